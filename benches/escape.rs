@@ -3,8 +3,7 @@ extern crate test;
 extern crate marksman_escape;
 
 use test::{Bencher, black_box};
-use marksman_escape::Escape;
-use std::str::StrExt;
+use marksman_escape::{Escape, escape_str};
 
 
 static MX_ESCAPES : &'static str = "a<cd>f{bcd}f<bcd>f{bc}e'ab\"d?f@b!d`f{bcd}f&b=d+f(bcd)f%bc$e!
@@ -29,6 +28,14 @@ fn escape_mixed(b: &mut Bencher){
     });
 }
 
+#[bench]
+fn escape_mixed_slice(b: &mut Bencher){
+    b.bytes = MX_ESCAPES.len() as u64;
+    b.iter(||{
+        black_box(escape_str(black_box(MX_ESCAPES)));
+    });
+}
+
 
 static NO_ESCAPES : &'static str = r##"abcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdef
 fabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdeffabcdefabcdefabcdefabcdefabcdefabcdefabcdefa
@@ -64,6 +71,14 @@ fn escape_no_spec(b: &mut Bencher){
     });
 }
 
+#[bench]
+fn escape_no_spec_slice(b: &mut Bencher){
+    b.bytes = NO_ESCAPES.len() as u64;
+    b.iter(||{
+        black_box(escape_str(black_box(NO_ESCAPES)));
+    });
+}
+
 
 static ALL_ESCAPES_SHORT : &'static str = r##"<><><><><><><><><><><><><><><><><><><><><><><><><><><
 ><><><><><><><><><><><><><><><><><><><><><><><><><><><><><><><><><><><><><><><><><><><><><><><><><>
@@ -83,6 +98,14 @@ fn escape_spec_short(b: &mut Bencher){
     });
 }
 
+#[bench]
+fn escape_spec_short_slice(b: &mut Bencher){
+    b.bytes = ALL_ESCAPES_SHORT.len() as u64;
+    b.iter(||{
+        black_box(escape_str(black_box(ALL_ESCAPES_SHORT)));
+    });
+}
+
 
 static ALL_ESCAPES_LONG : &'static str = "[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}
 []{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]{}[]
@@ -2,9 +2,8 @@
 extern crate test;
 extern crate marksman_escape;
 
-use test::Bencher;
-use marksman_escape::{Unescape, get_named_ref};
-use std::str::StrExt;
+use test::{Bencher, black_box};
+use marksman_escape::{Unescape, get_named_ref, unescape_str};
 
 static NO_ESCAPES : &'static str = r##"abcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdef
 fabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdefabcdeffabcdefabcdefabcdefabcdefabcdefabcdefabcdefa
@@ -33,6 +32,14 @@ fn unescape_no_spec(b: &mut Bencher){
     });
 }
 
+#[bench]
+fn unescape_no_spec_slice(b: &mut Bencher){
+    b.bytes = NO_ESCAPES.len() as u64;
+    b.iter(||{
+        black_box(unescape_str(black_box(NO_ESCAPES)));
+    });
+}
+
 
 
 static ALL_ESCAPES_NAMED : &'static str = r##"&lt;&gt;&amp;&lt&gt&amp&lt;&gt;&amp;&lt&gt&amp
@@ -54,6 +61,14 @@ fn unescape_spec_named(b: &mut Bencher){
     });
 }
 
+#[bench]
+fn unescape_spec_named_slice(b: &mut Bencher){
+    b.bytes = ALL_ESCAPES_NAMED.len() as u64;
+    b.iter(||{
+        black_box(unescape_str(black_box(ALL_ESCAPES_NAMED)));
+    });
+}
+
 
 
 static ALL_ESCAPES_NUM : &'static str = r##"&#91;&#93;&#123;&#125;&#91;&#93;&#123;&#125;&#91;&#93;
@@ -74,6 +89,14 @@ fn unescape_spec_num(b: &mut Bencher){
     });
 }
 
+#[bench]
+fn unescape_spec_num_slice(b: &mut Bencher){
+    b.bytes = ALL_ESCAPES_NUM.len() as u64;
+    b.iter(||{
+        black_box(unescape_str(black_box(ALL_ESCAPES_NUM)));
+    });
+}
+
 static ALL_ESCAPES_HEX : &'static str = r##"&#x5B;&#x5d;&#x7B;&#7d;&#x5B;&#x5d;&#x7B;&#7d;&#x5B;
 &#x5B;&#x5d;&#x7B;&#7d;&#x5B;&#x5d;&#x7B;&#7d;&#x5B;&#x5B;&#x5d;&#x7B;&#7d;&#x5B;&#x5d;&#x7B;&#7d;
 &#x5B;&#x5d;&#x7B;&#7d;&#x5B;&#x5d;&#x7B;&#7d;&#x5B;&#x5B;&#x5d;&#x7B;&#7d;&#x5B;&#x5d;&#x7B;&#7d;
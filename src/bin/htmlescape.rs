@@ -1,14 +1,21 @@
 extern crate marksman_escape;
 
-use marksman_escape::Escape;
-use std::io::{stdin, stdout, Read, Write};
+use marksman_escape::EscapeWriter;
+use std::io::{self, stdin, stdout, Write};
+use std::process;
 
 fn main(){
     let stdin = stdin();
     let stdout = stdout();
-    let mut lstdout = stdout.lock();
+    let mut writer = EscapeWriter::new(stdout.lock());
 
-    for escaped in Escape::new(stdin.lock().bytes().map(|x|{ x.unwrap() })) {
-        let _ = lstdout.write(&[escaped; 1]);
+    match io::copy(&mut stdin.lock(), &mut writer).and_then(|_| writer.flush()) {
+        Ok(()) => {},
+        // The output was closed early, e.g. by `htmlescape | head`.
+        Err(ref e) if e.kind() == io::ErrorKind::BrokenPipe => {},
+        Err(e) => {
+            eprintln!("htmlescape: {}", e);
+            process::exit(1);
+        }
     }
 }
@@ -1,14 +1,24 @@
 extern crate marksman_escape;
 
-use marksman_escape::Unescape;
-use std::io::{stdin, stdout, Read, Write};
+use marksman_escape::UnescapeWriter;
+use std::io::{self, stdin, stdout, Write};
+use std::process;
 
 fn main(){
     let stdin = stdin();
     let stdout = stdout();
-    let mut lstdout = stdout.lock();
+    let mut writer = UnescapeWriter::new(stdout.lock());
 
-    for unescaped in Unescape::new(stdin.lock().bytes().map(|x|{ x.unwrap() })) {
-        let _ = lstdout.write(&[unescaped; 1]);
+    let result = io::copy(&mut stdin.lock(), &mut writer)
+        .and_then(|_| writer.into_inner())
+        .and_then(|mut stdout| stdout.flush());
+    match result {
+        Ok(()) => {},
+        // The output was closed early, e.g. by `htmlunescape | head`.
+        Err(ref e) if e.kind() == io::ErrorKind::BrokenPipe => {},
+        Err(e) => {
+            eprintln!("htmlunescape: {}", e);
+            process::exit(1);
+        }
     }
 }
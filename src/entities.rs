@@ -0,0 +1,121 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+/// A dictionary of named references, such as the entities declared in the internal subset of a
+/// DTD.
+///
+/// The dictionary is given to `Unescape::with_entities` and its names are recognised alongside
+/// (or instead of) the built-in ones. Unlike the built-in HTML5 names, these must always be
+/// terminated with a `;`. Where a name is also a built-in one, the dictionary takes precedence.
+///
+/// # Usage
+///
+/// ```
+/// use marksman_escape::{Entities, Unescape};
+/// let entities = Entities::new().entity("company", "Example & Co.");
+/// let string = "&copy; &company;";
+/// let unescaped = Unescape::new(string.bytes()).with_entities(entities).collect::<Vec<_>>();
+/// assert_eq!(b"\xc2\xa9 Example & Co.", &*unescaped);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Entities {
+    names: BTreeMap<Vec<u8>, Vec<u8>>,
+    builtin: bool,
+    longest: usize,
+    longest_replacement: usize,
+}
+
+impl Entities {
+    /// Create an empty dictionary, which is used alongside the built-in names.
+    pub fn new() -> Entities {
+        Entities {
+            names: BTreeMap::new(),
+            builtin: true,
+            longest: 0,
+            longest_replacement: 0,
+        }
+    }
+
+    /// Create an empty dictionary, which is used instead of the built-in names.
+    ///
+    /// Only the numeric references and the names in the dictionary are then recognised.
+    pub fn exclusive() -> Entities {
+        Entities {
+            builtin: false,
+            ..Entities::new()
+        }
+    }
+
+    /// Replace references to `name`, without the `&` and `;`, with `replacement`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is not an XML name, e.g. if it is empty, starts with a digit or contains
+    /// whitespace.
+    pub fn entity(mut self, name: &str, replacement: &str) -> Entities {
+        assert!(name.chars().next().is_some_and(is_name_start_char),
+                "name must start with a letter, `_` or `:`");
+        assert!(name.chars().all(is_name_char), "name must only contain XML name characters");
+        let name = name.as_bytes();
+        self.longest = self.longest.max(name.len());
+        self.longest_replacement = self.longest_replacement.max(replacement.len());
+        self.names.insert(name.to_vec(), replacement.as_bytes().to_vec());
+        self
+    }
+
+    /// Replacement of the reference to `name`, if it is in the dictionary.
+    pub(crate) fn get(&self, name: &[u8]) -> Option<&[u8]> {
+        self.names.get(name).map(|replacement| &replacement[..])
+    }
+
+    /// Whether the built-in names are recognised as well.
+    pub(crate) fn builtin(&self) -> bool {
+        self.builtin
+    }
+
+    /// Length of the longest name in the dictionary.
+    pub(crate) fn longest(&self) -> usize {
+        self.longest
+    }
+
+    /// Length of the longest replacement in the dictionary.
+    pub(crate) fn longest_replacement(&self) -> usize {
+        self.longest_replacement
+    }
+}
+
+impl Default for Entities {
+    fn default() -> Entities {
+        Entities::new()
+    }
+}
+
+/// Whether the character may start an XML name.
+fn is_name_start_char(ch: char) -> bool {
+    matches!(ch, ':' | 'A'..='Z' | '_' | 'a'..='z' | '\u{C0}'..='\u{D6}' | '\u{D8}'..='\u{F6}'
+             | '\u{F8}'..='\u{2FF}' | '\u{370}'..='\u{37D}' | '\u{37F}'..='\u{1FFF}'
+             | '\u{200C}'..='\u{200D}' | '\u{2070}'..='\u{218F}' | '\u{2C00}'..='\u{2FEF}'
+             | '\u{3001}'..='\u{D7FF}' | '\u{F900}'..='\u{FDCF}' | '\u{FDF0}'..='\u{FFFD}'
+             | '\u{10000}'..='\u{EFFFF}')
+}
+
+/// Whether the character may appear in an XML name.
+pub(crate) fn is_name_char(ch: char) -> bool {
+    is_name_start_char(ch)
+        || matches!(ch, '-' | '.' | '0'..='9' | '\u{B7}' | '\u{300}'..='\u{36F}'
+                    | '\u{203F}'..='\u{2040}')
+}
+
+/// Whether the byte may start a name.
+///
+/// Every byte of a non-ASCII character is accepted, the name is only checked when it is looked up.
+pub(crate) fn is_name_start_byte(byte: u8) -> bool {
+    byte.is_ascii_alphabetic() || byte == b'_' || byte == b':' || byte >= 0x80
+}
+
+/// Whether the byte may appear in a name after the first character.
+///
+/// Every byte of a non-ASCII character is accepted, the name is only checked when it is looked up.
+pub(crate) fn is_name_byte(byte: u8) -> bool {
+    is_name_start_byte(byte) || byte.is_ascii_digit() || byte == b'-' || byte == b'.'
+}
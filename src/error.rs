@@ -0,0 +1,127 @@
+use core::error::Error;
+use core::fmt;
+
+
+/// A kind of malformed character reference.
+///
+/// These correspond to the [parse errors][html5-errors] the HTML5 spec defines for character
+/// references.
+///
+/// [html5-errors]: https://html.spec.whatwg.org/multipage/parsing.html#parse-errors
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum UnescapeErrorKind {
+    /// A reference without the terminating `;`, e.g. `&amp` or `&#38`.
+    MissingSemicolon,
+    /// A name terminated with `;` which is not a known reference name, e.g. `&bogus;`.
+    UnknownNamedReference,
+    /// A numeric reference without any digits, e.g. `&#;` or `&#x;`.
+    AbsenceOfDigits,
+    /// A numeric reference to U+0000.
+    NullCharacter,
+    /// A numeric reference to a value beyond U+10FFFF.
+    OutsideUnicodeRange,
+    /// A numeric reference to a surrogate, U+D800 to U+DFFF.
+    Surrogate,
+    /// A numeric reference to a noncharacter, such as U+FFFE.
+    Noncharacter,
+    /// A numeric reference to a control character other than whitespace, such as U+0080.
+    ControlCharacter,
+}
+
+impl UnescapeErrorKind {
+    fn description(self) -> &'static str {
+        match self {
+            UnescapeErrorKind::MissingSemicolon => "missing semicolon after character reference",
+            UnescapeErrorKind::UnknownNamedReference => "unknown named character reference",
+            UnescapeErrorKind::AbsenceOfDigits =>
+                "absence of digits in numeric character reference",
+            UnescapeErrorKind::NullCharacter => "null character reference",
+            UnescapeErrorKind::OutsideUnicodeRange => "character reference outside unicode range",
+            UnescapeErrorKind::Surrogate => "surrogate character reference",
+            UnescapeErrorKind::Noncharacter => "noncharacter character reference",
+            UnescapeErrorKind::ControlCharacter => "control character reference",
+        }
+    }
+}
+
+/// A malformed character reference found while unescaping.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct UnescapeError {
+    offset: usize,
+    len: usize,
+    kind: UnescapeErrorKind,
+}
+
+impl UnescapeError {
+    #[cfg(feature = "alloc")]
+    pub(crate) fn new(offset: usize, len: usize, kind: UnescapeErrorKind) -> UnescapeError {
+        UnescapeError {
+            offset,
+            len,
+            kind,
+        }
+    }
+
+    /// Offset of the `&` starting the malformed reference in the input.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Length of the malformed reference in the input.
+    ///
+    /// The reference itself is `&input[error.offset()..error.offset() + error.len()]`.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// What is wrong with the reference.
+    pub fn kind(&self) -> UnescapeErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for UnescapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at bytes {}..{}", self.kind.description(), self.offset,
+               self.offset + self.len)
+    }
+}
+
+impl Error for UnescapeError {}
+
+/// A character which is not allowed in XML 1.0 documents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct IllegalXmlChar {
+    offset: usize,
+    ch: char,
+}
+
+impl IllegalXmlChar {
+    #[cfg(feature = "alloc")]
+    pub(crate) fn new(offset: usize, ch: char) -> IllegalXmlChar {
+        IllegalXmlChar {
+            offset,
+            ch,
+        }
+    }
+
+    /// Offset of the character in the input.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The character.
+    pub fn char(&self) -> char {
+        self.ch
+    }
+}
+
+impl fmt::Display for IllegalXmlChar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "character U+{:04X} is not allowed in XML at byte {}", self.ch as u32,
+               self.offset)
+    }
+}
+
+impl Error for IllegalXmlChar {}
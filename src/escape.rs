@@ -1,8 +1,16 @@
-const LONGEST_ESCAPE : usize = 6;
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::{cmp, fmt};
+use core::str;
+#[cfg(feature = "alloc")]
+use core::ptr;
+
+use scan::ByteSet;
 
 /// Escape a byte stream with HTML-encoded variants of certain characters.
 ///
-/// Current implementation escapes the following list of characters:
+/// Unless a narrower `EscapeContext` is chosen, current implementation escapes the following list
+/// of characters:
 ///
 /// <table>
 ///     <tr>
@@ -35,15 +43,567 @@ const LONGEST_ESCAPE : usize = 6;
 /// The implementation works with bytes interpreting them to be ASCII, which means that any
 /// ASCII-compatible encoding, including UTF-8, is supported.
 #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
-pub struct Escape<I: Iterator<Item=u8>> {
+pub struct Escape<'t, I: Iterator<Item=u8>> {
     inner: I,
-    buffer: u64
+    /// Rest of the replacement being emitted.
+    pending: &'t [u8],
+    table: &'t EscapeTable<'t>
+}
+
+/// Escape a stream of characters with HTML-encoded variants of certain characters.
+///
+/// Same as `Escape`, except that it works with characters rather than bytes.
+///
+/// # Usage
+///
+/// ```
+/// use marksman_escape::EscapeChars;
+/// let string = "<wörld>";
+/// let escaped = EscapeChars::new(string.chars()).collect::<String>();
+/// assert_eq!("&lt;wörld&gt;", &*escaped);
+/// ```
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct EscapeChars<'t, I: Iterator<Item=char>> {
+    inner: I,
+    /// Rest of the replacement being emitted.
+    pending: &'t str,
+    table: &'t EscapeTable<'t>
+}
+
+/// The context escaped output is going to be inserted into.
+///
+/// Every context escapes only the minimal set of characters necessary to keep the output from
+/// breaking out of that context. Use `Escape::new` if the context is not known in advance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EscapeContext {
+    /// Text between tags, e.g. `<p>…</p>`. Escapes `&`, `<` and `>`.
+    Text,
+    /// Attribute value enclosed in double quotes, e.g. `<a href="…">`. Escapes `&` and `"`.
+    DoubleQuotedAttr,
+    /// Attribute value enclosed in single quotes, e.g. `<a href='…'>`. Escapes `&` and `'`.
+    SingleQuotedAttr,
+    /// Attribute value without quotes, e.g. `<a href=…>`. Escapes `&`, `<`, `>`, `"`, `'`, `` ` ``,
+    /// `=` and whitespace.
+    UnquotedAttr,
+}
+
+impl EscapeContext {
+    /// The escape table used for this context.
+    pub fn table(self) -> &'static EscapeTable<'static> {
+        match self {
+            EscapeContext::Text => &TEXT,
+            EscapeContext::DoubleQuotedAttr => &DOUBLE_QUOTED_ATTR,
+            EscapeContext::SingleQuotedAttr => &SINGLE_QUOTED_ATTR,
+            EscapeContext::UnquotedAttr => &UNQUOTED_ATTR,
+        }
+    }
+}
+
+/// A table mapping ASCII bytes to their replacements.
+///
+/// Bytes without a replacement, as well as all the non-ASCII bytes, are passed through unchanged.
+/// The replacements are borrowed for as long as the table lives and may be of any length.
+///
+/// # Usage
+///
+/// ```
+/// use marksman_escape::{Escape, EscapeContext};
+/// let table = EscapeContext::Text.table().to_builder()
+///                                        .escape(b'/', "&#47;")
+///                                        .keep(b'>')
+///                                        .build();
+/// let escaped = Escape::with_table("</a>".bytes(), &table).collect();
+/// assert_eq!("&lt;&#47;a>", String::from_utf8(escaped).unwrap());
+/// ```
+#[derive(Clone)]
+pub struct EscapeTable<'r> {
+    /// Replacement of every ASCII byte, empty for the bytes left alone.
+    replacements: [&'r str; 128],
+    /// Length of the longest replacement, or 1 if there are none.
+    longest: usize,
+    /// The bytes with a replacement, for the slice APIs to skip over the others in bulk.
+    escaped: ByteSet,
+    /// Every ASCII byte or its replacement in the low bytes of a word and its length in the top
+    /// byte, for the slice APIs to copy a word at a time, if no replacement is longer than seven.
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    packed: [u64; 128],
+}
+
+impl<'r> EscapeTable<'r> {
+    const fn from_replacements(replacements: [&'r str; 128]) -> EscapeTable<'r> {
+        let mut escaped = [false; 128];
+        let mut longest = 1;
+        let mut byte = 0;
+        while byte < replacements.len() {
+            escaped[byte] = !replacements[byte].is_empty();
+            if replacements[byte].len() > longest {
+                longest = replacements[byte].len();
+            }
+            byte += 1;
+        }
+        let mut packed = [0; 128];
+        byte = 0;
+        while byte < replacements.len() && longest < 8 {
+            let replacement = replacements[byte].as_bytes();
+            if replacement.is_empty() {
+                packed[byte] = 1 << 56 | byte as u64;
+            } else {
+                packed[byte] = (replacement.len() as u64) << 56;
+                let mut i = 0;
+                while i < replacement.len() {
+                    packed[byte] |= (replacement[i] as u64) << (8 * i);
+                    i += 1;
+                }
+            }
+            byte += 1;
+        }
+        EscapeTable {
+            replacements,
+            longest,
+            escaped: ByteSet::new(&escaped),
+            packed,
+        }
+    }
+
+    /// Create a builder for a table which escapes nothing.
+    pub fn builder() -> EscapeTableBuilder<'r> {
+        EscapeTableBuilder([""; 128])
+    }
+
+    /// Create a builder for a table which escapes the same bytes as this table does.
+    pub fn to_builder(&self) -> EscapeTableBuilder<'r> {
+        EscapeTableBuilder(self.replacements)
+    }
+
+    /// The table used by `Escape::new`.
+    pub fn conservative() -> &'static EscapeTable<'static> {
+        &CONSERVATIVE
+    }
+
+    /// Escape a byte slice according to this table.
+    ///
+    /// The slice is returned as is if it contains no bytes to escape.
+    #[cfg(feature = "alloc")]
+    pub fn escape_bytes<'a>(&self, bytes: &'a [u8]) -> Cow<'a, [u8]> {
+        let first = match self.escaped.find(bytes) {
+            Some(first) => first,
+            None => return Cow::Borrowed(bytes)
+        };
+        let mut out = Vec::with_capacity(first + self.escaped_len(&bytes[first..]));
+        out.extend_from_slice(&bytes[..first]);
+        self.escape_to(&bytes[first..], &mut out);
+        Cow::Owned(out)
+    }
+
+    /// Escape a string slice according to this table.
+    ///
+    /// The slice is returned as is if it contains no characters to escape.
+    #[cfg(feature = "alloc")]
+    pub fn escape_str<'a>(&self, string: &'a str) -> Cow<'a, str> {
+        match self.escape_bytes(string.as_bytes()) {
+            Cow::Borrowed(_) => Cow::Borrowed(string),
+            // Only ASCII bytes are replaced and all the replacements are valid UTF-8.
+            Cow::Owned(v) => Cow::Owned(unsafe { String::from_utf8_unchecked(v) })
+        }
+    }
+
+    /// Length of the byte slice after escaping it according to this table.
+    pub fn escaped_len(&self, bytes: &[u8]) -> usize {
+        let (mut len, mut i) = (0, 0);
+        while let Some(found) = self.escaped.find(&bytes[i..]) {
+            len += found;
+            i += found;
+            // Bytes to escape tend to come in clusters, where looking every byte up in the table
+            // is quicker than searching for the next one, up to a block with none left.
+            loop {
+                let block = &bytes[i..cmp::min(i + DENSE_BLOCK, bytes.len())];
+                let escaped = block.iter().map(|&byte| self.get(byte).len().max(1)).sum::<usize>();
+                len += escaped;
+                i += block.len();
+                if escaped == DENSE_BLOCK || block.len() < DENSE_BLOCK {
+                    break;
+                }
+            }
+        }
+        len + bytes.len() - i
+    }
+
+    /// Length of the longest replacement in the table, or 1 if the table is empty.
+    pub(crate) fn longest(&self) -> usize {
+        self.longest
+    }
+
+    /// Append the escaped bytes to the vector.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn escape_to(&self, bytes: &[u8], out: &mut Vec<u8>) {
+        let mut i = 0;
+        while let Some(found) = self.escaped.find(&bytes[i..]) {
+            out.extend_from_slice(&bytes[i..i + found]);
+            i += found;
+            // Same clusters as in `escaped_len`.
+            loop {
+                let block = &bytes[i..cmp::min(i + DENSE_BLOCK, bytes.len())];
+                let len = out.len();
+                out.reserve(block.len() * self.longest);
+                // The room for the block escaped was reserved above.
+                unsafe {
+                    let written = self.escape_block(block, out.as_mut_ptr().add(len),
+                                                    out.capacity() - len);
+                    out.set_len(len + written);
+                }
+                i += block.len();
+                if out.len() - len == DENSE_BLOCK || block.len() < DENSE_BLOCK {
+                    break;
+                }
+            }
+        }
+        out.extend_from_slice(&bytes[i..]);
+    }
+
+    /// Write the escaped block to `dst`, which has room for `spare` bytes and at least `longest`
+    /// for every byte of the block, and return the number of bytes written.
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    unsafe fn escape_block(&self, block: &[u8], dst: *mut u8, spare: usize) -> usize {
+        let mut end = dst;
+        if self.longest < 8 && spare >= block.len() * self.longest + 8 {
+            // Every write fills a whole word, and the bytes past the replacement are overwritten by
+            // the next one or left in the spare capacity.
+            for &byte in block {
+                let word = match self.packed.get(byte as usize) {
+                    Some(&word) => word,
+                    None => 1 << 56 | byte as u64
+                };
+                end.cast::<u64>().write_unaligned(word.to_le());
+                end = end.add((word >> 56) as usize);
+            }
+        } else {
+            for &byte in block {
+                let escaped = self.get(byte);
+                if escaped.is_empty() {
+                    *end = byte;
+                    end = end.add(1);
+                } else {
+                    copy_short(escaped.as_bytes(), end);
+                    end = end.add(escaped.len());
+                }
+            }
+        }
+        end.offset_from(dst) as usize
+    }
+
+    /// Write the escaped string to the formatter, a run of unescaped characters at a time.
+    pub(crate) fn write_str(&self, string: &str, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut start = 0;
+        self.escaped.try_for_each(string.as_bytes(), |i| {
+            f.write_str(&string[start..i])?;
+            f.write_str(self.get(string.as_bytes()[i]))?;
+            // Only ASCII bytes are escaped, so the next one starts a character.
+            start = i + 1;
+            Ok(())
+        })?;
+        f.write_str(&string[start..])
+    }
+
+    /// Replacement of the byte, or an empty string if the byte should be left alone.
+    #[inline]
+    pub(crate) fn get(&self, byte: u8) -> &'r str {
+        match self.replacements.get(byte as usize) {
+            Some(&escaped) => escaped,
+            None => ""
+        }
+    }
+}
+
+/// Builder for `EscapeTable`.
+#[derive(Clone)]
+pub struct EscapeTableBuilder<'r>([&'r str; 128]);
+
+impl<'r> EscapeTableBuilder<'r> {
+    /// Replace `byte` with `replacement`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `byte` is not ASCII or if `replacement` is empty.
+    pub fn escape(mut self, byte: u8, replacement: &'r str) -> EscapeTableBuilder<'r> {
+        assert!(byte < 0x80, "only ASCII bytes can be escaped");
+        assert!(!replacement.is_empty(), "replacement must not be empty");
+        self.0[byte as usize] = replacement;
+        self
+    }
+
+    /// Pass `byte` through unchanged.
+    pub fn keep(mut self, byte: u8) -> EscapeTableBuilder<'r> {
+        if let Some(escaped) = self.0.get_mut(byte as usize) {
+            *escaped = "";
+        }
+        self
+    }
+
+    /// Build the table.
+    pub fn build(self) -> EscapeTable<'r> {
+        EscapeTable::from_replacements(self.0)
+    }
+}
+
+/// Copy the bytes to `dst`, with a couple of overlapping word moves for slices as short as the
+/// usual replacements rather than a call to `memcpy`.
+#[cfg(feature = "alloc")]
+#[inline(always)]
+unsafe fn copy_short(bytes: &[u8], dst: *mut u8) {
+    let (src, n) = (bytes.as_ptr(), bytes.len());
+    if n > 16 {
+        ptr::copy_nonoverlapping(src, dst, n);
+    } else if n >= 8 {
+        let (head, tail) = (src.cast::<u64>().read_unaligned(),
+                            src.add(n - 8).cast::<u64>().read_unaligned());
+        dst.cast::<u64>().write_unaligned(head);
+        dst.add(n - 8).cast::<u64>().write_unaligned(tail);
+    } else if n >= 4 {
+        let (head, tail) = (src.cast::<u32>().read_unaligned(),
+                            src.add(n - 4).cast::<u32>().read_unaligned());
+        dst.cast::<u32>().write_unaligned(head);
+        dst.add(n - 4).cast::<u32>().write_unaligned(tail);
+    } else if n > 0 {
+        *dst = *src;
+        *dst.add(n / 2) = *src.add(n / 2);
+        *dst.add(n - 1) = *src.add(n - 1);
+    }
+}
+
+/// Number of bytes the slice APIs look up in the table at a time once they find one to escape,
+/// until a whole block has none and they go back to searching.
+const DENSE_BLOCK : usize = 16;
+
+/// Build a table replacing every byte in the list with its replacement, for the static tables.
+pub(crate) const fn table(escapes: &[(u8, &'static str)]) -> EscapeTable<'static> {
+    let mut table = [""; 128];
+    let mut i = 0;
+    while i < escapes.len() {
+        table[escapes[i].0 as usize] = escapes[i].1;
+        i += 1;
+    }
+    EscapeTable::from_replacements(table)
+}
+
+static CONSERVATIVE: EscapeTable<'static> = table(&[
+    // Basic escapes
+    (b'&', "&amp;"), (b'>', "&gt;"), (b'<', "&lt;"),
+    (b'"', "&#34;"), (b'\'', "&#39;"), (b'`', "&#96;"),
+    // These only matter in cases where attributes are not quoted.
+    (b'!', "&#33;"), (b'$', "&#36;"), (b'%', "&#37;"), (b'(', "&#40;"), (b')', "&#41;"),
+    (b'+', "&#43;"), (b'=', "&#61;"), (b'@', "&#64;"), (b'[', "&#91;"), (b']', "&#93;"),
+    (b'{', "&#123;"), (b'}', "&#125;"),
+]);
+
+static TEXT: EscapeTable<'static> = table(&[
+    (b'&', "&amp;"), (b'<', "&lt;"), (b'>', "&gt;"),
+]);
+
+static DOUBLE_QUOTED_ATTR: EscapeTable<'static> = table(&[
+    (b'&', "&amp;"), (b'"', "&#34;"),
+]);
+
+static SINGLE_QUOTED_ATTR: EscapeTable<'static> = table(&[
+    (b'&', "&amp;"), (b'\'', "&#39;"),
+]);
+
+static UNQUOTED_ATTR: EscapeTable<'static> = table(&[
+    (b'&', "&amp;"), (b'<', "&lt;"), (b'>', "&gt;"),
+    (b'"', "&#34;"), (b'\'', "&#39;"), (b'`', "&#96;"), (b'=', "&#61;"),
+    // Whitespace terminates an unquoted attribute value.
+    (b'\t', "&#9;"), (b'\n', "&#10;"), (b'\x0C', "&#12;"), (b'\r', "&#13;"), (b' ', "&#32;"),
+]);
+
+
+/// Escape a byte slice with the same table `Escape::new` uses.
+///
+/// Unlike `Escape`, the slice is processed in bulk and no allocation happens if there is nothing
+/// to escape.
+///
+/// # Usage
+///
+/// ```
+/// use marksman_escape::escape_bytes;
+/// assert_eq!(&*escape_bytes(b"<hello>"), b"&lt;hello&gt;");
+/// assert_eq!(&*escape_bytes(b"hello"), b"hello");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn escape_bytes<'a>(bytes: &'a [u8]) -> Cow<'a, [u8]> {
+    CONSERVATIVE.escape_bytes(bytes)
+}
+
+/// Length of the byte slice after escaping it with the same table `Escape::new` uses.
+///
+/// The slice APIs use this to allocate the output exactly once.
+///
+/// # Usage
+///
+/// ```
+/// use marksman_escape::{escape_bytes, escaped_len};
+/// assert_eq!(escaped_len(b"<hello>"), escape_bytes(b"<hello>").len());
+/// ```
+pub fn escaped_len(bytes: &[u8]) -> usize {
+    CONSERVATIVE.escaped_len(bytes)
+}
+
+/// Escape a string slice with the same table `Escape::new` uses.
+///
+/// Unlike `Escape`, the slice is processed in bulk and no allocation happens if there is nothing
+/// to escape.
+///
+/// # Usage
+///
+/// ```
+/// use marksman_escape::escape_str;
+/// assert_eq!(escape_str("<hello>&world</hello>"), "&lt;hello&gt;&amp;world&lt;/hello&gt;");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn escape_str<'a>(string: &'a str) -> Cow<'a, str> {
+    CONSERVATIVE.escape_str(string)
+}
+
+/// A string slice which is escaped when it is formatted, with the same table `Escape::new` uses.
+///
+/// The escaped output is written straight to the formatter, without allocating.
+///
+/// # Usage
+///
+/// ```
+/// use marksman_escape::Escaped;
+/// let name = "<script>";
+/// assert_eq!(format!("<p>{}</p>", Escaped(name)), "<p>&lt;script&gt;</p>");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Escaped<'a>(pub &'a str);
+
+impl<'a> fmt::Display for Escaped<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        CONSERVATIVE.write_str(self.0, f)
+    }
 }
 
+/// A byte slice which is escaped when it is formatted, with the same table `Escape::new` uses.
+///
+/// The bytes are interpreted to be UTF-8 and every invalid UTF-8 sequence is written as U+FFFD
+/// REPLACEMENT CHARACTER, like `String::from_utf8_lossy` does.
+///
+/// # Usage
+///
+/// ```
+/// use marksman_escape::EscapedBytes;
+/// assert_eq!(format!("<p>{}</p>", EscapedBytes(b"a&b\xFF")), "<p>a&amp;b\u{FFFD}</p>");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct EscapedBytes<'a>(pub &'a [u8]);
 
-impl<I: Iterator<Item=u8>> Escape<I> {
+impl<'a> fmt::Display for EscapedBytes<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for chunk in self.0.utf8_chunks() {
+            CONSERVATIVE.write_str(chunk.valid(), f)?;
+            if !chunk.invalid().is_empty() {
+                f.write_str("\u{FFFD}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Escape a byte stream into fixed-size output buffers, without allocating.
+///
+/// The input and output are passed to `encode` a buffer at a time. When the output buffer fills
+/// up halfway through a replacement, the rest of it is kept and written first on the next call,
+/// so the output is the same as `Escape` would produce for all of the input concatenated.
+///
+/// # Usage
+///
+/// ```
+/// use marksman_escape::Escaper;
+/// let mut escaper = Escaper::new();
+/// let mut output = [0; 8];
+/// assert_eq!(escaper.encode(b"a(b)c", &mut output), (4, 8));
+/// assert_eq!(&output, b"a&#40;b&");
+/// assert_eq!(escaper.encode(b"c", &mut output), (1, 5));
+/// assert_eq!(&output[..5], b"#41;c");
+/// ```
+#[derive(Clone)]
+pub struct Escaper<'t> {
+    /// Rest of the replacement which did not fit into the output.
+    pending: &'t [u8],
+    table: &'t EscapeTable<'t>
+}
+
+impl<'t> Escaper<'t> {
+    /// Create an escaper which escapes the same bytes as `Escape::new` does.
+    pub fn new() -> Escaper<'t> {
+        Escaper::with_table(&CONSERVATIVE)
+    }
+
+    /// Create an escaper which will escape only the bytes which are significant in the given
+    /// context.
+    pub fn with_context(context: EscapeContext) -> Escaper<'t> {
+        Escaper::with_table(context.table())
+    }
+
+    /// Create an escaper which will escape the bytes according to the provided table.
+    pub fn with_table(table: &'t EscapeTable<'t>) -> Escaper<'t> {
+        Escaper {
+            pending: &[],
+            table
+        }
+    }
+
+    /// Escape as much of the input as fits into the output.
+    ///
+    /// Returns the number of bytes consumed from the input and written to the output. The input is
+    /// consumed completely unless the output fills up. The rest of a replacement which did not fit
+    /// is written by the next call, which may be given empty input to only do that.
+    pub fn encode(&mut self, input: &[u8], output: &mut [u8]) -> (usize, usize) {
+        let mut consumed = 0;
+        let mut written = self.write_pending(output);
+        while consumed < input.len() && written < output.len() {
+            let rest = &input[consumed..];
+            let rest = &rest[..rest.len().min(output.len() - written)];
+            let run = self.table.escaped.find(rest).unwrap_or(rest.len());
+            output[written..written + run].copy_from_slice(&rest[..run]);
+            consumed += run;
+            written += run;
+            if run < rest.len() {
+                self.pending = self.table.get(rest[run]).as_bytes();
+                consumed += 1;
+                written += self.write_pending(&mut output[written..]);
+            }
+        }
+        (consumed, written)
+    }
+
+    /// Whether the rest of a replacement is yet to be written.
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Write as much of the rest of the replacement as fits into the output.
+    fn write_pending(&mut self, output: &mut [u8]) -> usize {
+        let len = self.pending.len().min(output.len());
+        output[..len].copy_from_slice(&self.pending[..len]);
+        self.pending = &self.pending[len..];
+        len
+    }
+}
+
+impl<'t> Default for Escaper<'t> {
+    fn default() -> Escaper<'t> {
+        Escaper::new()
+    }
+}
+
+
+impl<'t, I: Iterator<Item=u8>> Escape<'t, I> {
     /// Create an iterator adaptor which will escape all the bytes of internal iterator.
     ///
+    /// The escaped output is safe to insert into any context, including unquoted attribute
+    /// values.
+    ///
     /// # Usage
     ///
     /// ```
@@ -52,47 +612,51 @@ impl<I: Iterator<Item=u8>> Escape<I> {
     /// let escaped = String::from_utf8(Escape::new(string.bytes()).collect()).unwrap();
     /// assert_eq!("&lt;hello&gt;&amp;world&lt;/hello&gt;", &*escaped);
     /// ```
-    pub fn new(i: I) -> Escape<I> {
+    pub fn new(i: I) -> Escape<'t, I> {
+        Escape::with_table(i, &CONSERVATIVE)
+    }
+
+    /// Create an iterator adaptor which will escape only the bytes which are significant in the
+    /// given context.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use marksman_escape::{Escape, EscapeContext};
+    /// let string = "a < b && (c = d)";
+    /// let escaped = Escape::with_context(string.bytes(), EscapeContext::Text).collect();
+    /// assert_eq!("a &lt; b &amp;&amp; (c = d)", String::from_utf8(escaped).unwrap());
+    /// ```
+    pub fn with_context(i: I, context: EscapeContext) -> Escape<'t, I> {
+        Escape::with_table(i, context.table())
+    }
+
+    /// Create an iterator adaptor which will escape the bytes of internal iterator according to
+    /// the provided table.
+    pub fn with_table(i: I, table: &'t EscapeTable<'t>) -> Escape<'t, I> {
         Escape {
             inner: i,
-            buffer: 0
+            pending: &[],
+            table
         }
     }
 }
 
-impl<I: Iterator<Item=u8>> Iterator for Escape<I> {
+impl<'t, I: Iterator<Item=u8>> Iterator for Escape<'t, I> {
     type Item = u8;
 
     fn next(&mut self) -> Option<u8> {
-        if self.buffer != 0 {
-            let ret = Some(self.buffer as u8);
-            self.buffer >>= 8;
-            ret
+        if let Some((&first, rest)) = self.pending.split_first() {
+            self.pending = rest;
+            Some(first)
         } else if let Some(ch) = self.inner.next() {
-            self.buffer = match ch {
-                // Basic escapes
-                b'&'  => 0x3b_70_6d_61,    // amp;
-                b'>'  => 0x3b_74_67,       // gt;
-                b'<'  => 0x3b_74_6c,       // lt;
-                b'"'  => 0x3b_34_33_23,    // #34;
-                b'\'' => 0x3b_39_33_23,    // #39;
-                b'`'  => 0x3b_36_39_23,    // #96;
-                // These only matter in cases where attributes are not quoted.
-                b'!'  => 0x3b_33_33_23,    // #33;
-                b'$'  => 0x3b_36_33_23,    // #36;
-                b'%'  => 0x3b_37_33_23,    // #37;
-                b'('  => 0x3b_30_34_23,    // #40;
-                b')'  => 0x3b_31_34_23,    // #41;
-                b'+'  => 0x3b_33_34_23,    // #43;
-                b'='  => 0x3b_31_36_23,    // #61;
-                b'@'  => 0x3b_34_36_23,    // #64;
-                b'['  => 0x3b_31_39_23,    // #91;
-                b']'  => 0x3b_33_39_23,    // #93;
-                b'{'  => 0x3b_33_32_31_23, // #123;
-                b'}'  => 0x3b_35_32_31_23, // #125;
-                _     => return Some(ch)
-            };
-            Some(b'&')
+            match self.table.get(ch).as_bytes().split_first() {
+                Some((&first, rest)) => {
+                    self.pending = rest;
+                    Some(first)
+                },
+                None => Some(ch)
+            }
         } else {
             None
         }
@@ -100,6 +664,258 @@ impl<I: Iterator<Item=u8>> Iterator for Escape<I> {
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         let (l, u) = self.inner.size_hint();
-        (l, if let Some(u_) = u {u_.checked_mul(LONGEST_ESCAPE)} else {None})
+        let buffered = self.pending.len();
+        let upper = u.and_then(|u_| u_.checked_mul(self.table.longest()))
+                     .and_then(|u_| u_.checked_add(buffered));
+        (l.saturating_add(buffered), upper)
+    }
+}
+
+impl<'t, I: Iterator<Item=char>> EscapeChars<'t, I> {
+    /// Create an iterator adaptor which will escape all the characters of internal iterator, like
+    /// `Escape::new` does.
+    pub fn new(i: I) -> EscapeChars<'t, I> {
+        EscapeChars::with_table(i, &CONSERVATIVE)
+    }
+
+    /// Create an iterator adaptor which will escape only the characters which are significant in
+    /// the given context.
+    pub fn with_context(i: I, context: EscapeContext) -> EscapeChars<'t, I> {
+        EscapeChars::with_table(i, context.table())
+    }
+
+    /// Create an iterator adaptor which will escape the characters of internal iterator according
+    /// to the provided table.
+    pub fn with_table(i: I, table: &'t EscapeTable<'t>) -> EscapeChars<'t, I> {
+        EscapeChars {
+            inner: i,
+            pending: "",
+            table
+        }
+    }
+}
+
+impl<'t, I: Iterator<Item=char>> Iterator for EscapeChars<'t, I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.pending.is_empty() {
+            let ch = self.inner.next()?;
+            if !ch.is_ascii() {
+                return Some(ch);
+            }
+            self.pending = self.table.get(ch as u8);
+            if self.pending.is_empty() {
+                return Some(ch);
+            }
+        }
+        let mut chars = self.pending.chars();
+        let ch = chars.next();
+        self.pending = chars.as_str();
+        ch
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (l, u) = self.inner.size_hint();
+        // At least one character is left in a non-empty replacement, and at most one per byte.
+        let buffered = self.pending.len();
+        let upper = u.and_then(|u_| u_.checked_mul(self.table.longest()))
+                     .and_then(|u_| u_.checked_add(buffered));
+        (l.saturating_add((buffered != 0) as usize), upper)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+    use std::prelude::v1::*;
+    use ::{Escape, EscapeChars, EscapeContext, EscapeTable, escape_bytes, escape_str};
+    use test_util::{check_size_hint, random};
+    fn run_test(context: EscapeContext, from: &str, to: &str) {
+        let ev = Escape::with_context(from.bytes(), context).collect::<Vec<_>>();
+        assert_eq!(String::from_utf8(ev).unwrap(), to);
+        assert_eq!(EscapeChars::with_context(from.chars(), context).collect::<String>(), to);
+    }
+
+    #[test]
+    fn conservative(){
+        let ev = Escape::new("<a href='#'>(@{x}=[$y])</a>".bytes()).collect::<Vec<_>>();
+        assert_eq!(String::from_utf8(ev).unwrap(),
+                   "&lt;a href&#61;&#39;#&#39;&gt;&#40;&#64;&#123;x&#125;&#61;&#91;&#36;y&#93;&#41;\
+                    &lt;/a&gt;");
+    }
+
+    #[test]
+    fn text(){
+        run_test(EscapeContext::Text, "Hello world!", "Hello world!");
+        run_test(EscapeContext::Text, "<b>'\"(@)=\"'</b>", "&lt;b&gt;'\"(@)=\"'&lt;/b&gt;");
+        run_test(EscapeContext::Text, "&amp;", "&amp;amp;");
+    }
+
+    #[test]
+    fn quoted_attr(){
+        run_test(EscapeContext::DoubleQuotedAttr, "<'\"&\"'>", "<'&#34;&amp;&#34;'>");
+        run_test(EscapeContext::SingleQuotedAttr, "<'\"&\"'>", "<&#39;\"&amp;\"&#39;>");
+    }
+
+    #[test]
+    fn unquoted_attr(){
+        run_test(EscapeContext::UnquotedAttr, "a=b c\td\ne", "a&#61;b&#32;c&#9;d&#10;e");
+        run_test(EscapeContext::UnquotedAttr, "`'\"<&>'`",
+                 "&#96;&#39;&#34;&lt;&amp;&gt;&#39;&#96;");
+        run_test(EscapeContext::UnquotedAttr, "(@)/[]", "(@)/[]");
+    }
+
+    #[test]
+    fn custom_table(){
+        let table = EscapeTable::builder().escape(b'/', "&#47;").escape(b'#', "&num;").build();
+        let ev = Escape::with_table("<a href=\"/#top\">".bytes(), &table).collect::<Vec<_>>();
+        assert_eq!(String::from_utf8(ev).unwrap(), "<a href=\"&#47;&num;top\">");
+
+        let table = EscapeTable::conservative().to_builder().keep(b'(').keep(b')').build();
+        let ev = Escape::with_table("f(@)".bytes(), &table).collect::<Vec<_>>();
+        assert_eq!(String::from_utf8(ev).unwrap(), "f(&#64;)");
+
+        let table = EscapeTable::builder().escape(b'\0', "\u{FFFD}").escape(b'-', "\u{2011}")
+                                          .build();
+        let ec = EscapeChars::with_table("a-\0b\u{2011}".chars(), &table).collect::<String>();
+        assert_eq!(ec, "a\u{2011}\u{FFFD}b\u{2011}");
+    }
+
+    #[test]
+    fn size_hint(){
+        let from = "<a href='#'>\u{e9}</a>";
+        let table = EscapeTable::builder().escape(b'&', "&amp;").escape(b'<', "\u{2039}").build();
+        for &table in &[EscapeTable::conservative(), EscapeContext::Text.table(), &table] {
+            let expected = table.escaped_len(from.as_bytes());
+            assert_eq!(expected, table.escape_bytes(from.as_bytes()).len());
+            check_size_hint(Escape::with_table(from.bytes(), table), expected);
+            let expected = String::from_utf8(table.escape_bytes(from.as_bytes()).into_owned())
+                                  .unwrap().chars().count();
+            check_size_hint(EscapeChars::with_table(from.chars(), table), expected);
+        }
+    }
+
+    #[test]
+    fn display(){
+        use std::fmt::Write;
+        use ::{Escaped, EscapedBytes};
+        let mut out = String::new();
+        write!(out, "<p>{}</p>", Escaped("<a href='#'>(@{x})</a>")).unwrap();
+        assert_eq!(out, "<p>&lt;a href&#61;&#39;#&#39;&gt;&#40;&#64;&#123;x&#125;&#41;&lt;/a&gt;\
+                         </p>");
+        assert_eq!(Escaped("héllo wörld").to_string(), "héllo wörld");
+        assert_eq!(Escaped("").to_string(), "");
+        assert_eq!(EscapedBytes("<é>".as_bytes()).to_string(), "&lt;é&gt;");
+        assert_eq!(EscapedBytes(b"\xC3<\xE2\x82\xAC\xED\xA0\x80>").to_string(),
+                   "\u{FFFD}&lt;\u{20AC}\u{FFFD}\u{FFFD}\u{FFFD}&gt;");
+    }
+
+    #[test]
+    fn custom_table_long_replacement(){
+        use ::{Escaper, EscapeAscii, EscapeNamed};
+        let smiley = String::from("&#x1F600;");
+        let table = EscapeTable::builder().escape(b'\n', "&NewLine;").escape(b'z', &smiley)
+                                          .escape(b'~', "&CounterClockwiseContourIntegral;")
+                                          .build();
+        let from = "a\nz~é";
+        let to = "a&NewLine;&#x1F600;&CounterClockwiseContourIntegral;é";
+        let ev = Escape::with_table(from.bytes(), &table).collect::<Vec<_>>();
+        assert_eq!(String::from_utf8(ev).unwrap(), to);
+        assert_eq!(EscapeChars::with_table(from.chars(), &table).collect::<String>(), to);
+        assert_eq!(table.escape_str(from), to);
+        assert_eq!(table.escaped_len(from.as_bytes()), to.len());
+        let mut output = [0; 64];
+        assert_eq!(Escaper::with_table(&table).encode(from.as_bytes(), &mut output),
+                   (from.len(), to.len()));
+        assert_eq!(&output[..to.len()], to.as_bytes());
+        let ev = EscapeAscii::with_table(from.bytes(), &table).collect::<Vec<_>>();
+        assert_eq!(String::from_utf8(ev).unwrap(), to.replace("é", "&#xE9;"));
+        let ev = EscapeNamed::with_table(from.bytes(), &table).collect::<Vec<_>>();
+        assert_eq!(String::from_utf8(ev).unwrap(), to.replace("é", "&eacute;"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn custom_table_empty_replacement(){
+        EscapeTable::builder().escape(b'/', "");
+    }
+
+    #[test]
+    fn slices(){
+        for s in &["", "Hello world", "<>", "a&b", "{x}", "ž<ž>ž", "&#123;&#125;", "trailing!"] {
+            let ev = Escape::new(s.bytes()).collect::<Vec<_>>();
+            assert_eq!(&*escape_bytes(s.as_bytes()), &*ev);
+            assert_eq!(escape_str(s), String::from_utf8(ev).unwrap());
+        }
+        assert!(matches!(escape_str("Hello world"), Cow::Borrowed(_)));
+        assert!(matches!(escape_str("Hello world!"), Cow::Owned(_)));
+        assert_eq!(EscapeContext::Text.table().escape_str("(a<b)"), "(a&lt;b)");
+    }
+
+    #[test]
+    fn escaper(){
+        use ::Escaper;
+        let from = "<a href='#'>(@{x})</a> \u{e9}&amp;".as_bytes();
+        let table = EscapeTable::builder().escape(b'&', "&amp;").escape(b'<', "\u{2039}").build();
+        for &table in &[EscapeTable::conservative(), EscapeContext::Text.table(), &table] {
+            let expected = Escape::with_table(from.iter().cloned(), table).collect::<Vec<_>>();
+            for size in 1..12 {
+                for chunk in 1..12 {
+                    let mut escaper = Escaper::with_table(table);
+                    let mut output = [0; 12];
+                    let mut escaped = Vec::new();
+                    for input in from.chunks(chunk) {
+                        let mut consumed = 0;
+                        while consumed < input.len() {
+                            let (c, w) = escaper.encode(&input[consumed..], &mut output[..size]);
+                            assert!(w == size || consumed + c == input.len());
+                            consumed += c;
+                            escaped.extend_from_slice(&output[..w]);
+                        }
+                    }
+                    while escaper.has_pending() {
+                        let (c, w) = escaper.encode(&[], &mut output[..size]);
+                        assert_eq!(c, 0);
+                        escaped.extend_from_slice(&output[..w]);
+                    }
+                    assert_eq!(escaped, expected);
+                }
+            }
+        }
+        assert_eq!(Escaper::new().encode(b"<", &mut []), (0, 0));
+    }
+
+    #[test]
+    fn slices_match_iterator(){
+        let custom = EscapeTable::builder().escape(b'\0', "\u{FFFD}").escape(b'a', "&#97;")
+                                           .escape(b'\x7F', "&#127;").build();
+        // Replacements of every length class copied differently, some too long to pack.
+        let long = EscapeTable::builder().escape(b'&', "&amp;amp;amp;amp;")
+                                         .escape(b'<', "&#x0003C;").escape(b'>', ">")
+                                         .escape(b'"', "''").build();
+        let tables = [EscapeTable::conservative(), EscapeContext::Text.table(),
+                      EscapeContext::DoubleQuotedAttr.table(), EscapeContext::UnquotedAttr.table(),
+                      &custom, &long];
+        // Mostly plain text with escapable bytes here and there, in runs of every length.
+        let alphabet = "abcdefgh ijklmnop\t<&>\"'=()é\u{0}\u{7F}\u{1F600}".chars()
+                                                                     .collect::<Vec<_>>();
+        let string = random().take(2000).map(|r| r % (alphabet.len() * 4))
+                             .map(|i| *alphabet.get(i).unwrap_or(&alphabet[i % 8]))
+                             .collect::<String>();
+        for &table in &tables {
+            for start in 0..40 {
+                let bytes = &string.as_bytes()[start..];
+                let ev = Escape::with_table(bytes.iter().cloned(), table).collect::<Vec<_>>();
+                assert_eq!(&*table.escape_bytes(bytes), &*ev);
+                assert_eq!(table.escaped_len(bytes), ev.len());
+                if let Some(s) = string.get(start..) {
+                    assert_eq!(table.escape_str(s).as_bytes(), &*ev);
+                }
+            }
+        }
+        let ev = Escape::new(string.bytes()).collect::<Vec<_>>();
+        assert_eq!(::Escaped(&string).to_string().as_bytes(), &*ev);
     }
 }
@@ -0,0 +1,243 @@
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String, vec::Vec};
+#[cfg(feature = "alloc")]
+use core::str::{self, Utf8Error};
+
+use escape::{EscapeContext, EscapeTable};
+use utf8::{LONGEST_HEX_REFERENCE, read_char, survives_reference, write_hex_reference};
+#[cfg(feature = "alloc")]
+use utf8::hex_reference_len;
+
+
+/// Escape a byte stream into pure 7-bit ASCII.
+///
+/// In addition to the ASCII characters the table escapes, every non-ASCII character is replaced
+/// with a hexadecimal character reference such as `&#xE9;`. The output is ASCII as long as the
+/// replacements in the table are.
+///
+/// The implementation works with bytes interpreting them to be UTF-8. Every invalid UTF-8 sequence
+/// is replaced with `&#xFFFD;`, the reference to U+FFFD REPLACEMENT CHARACTER, in the same places
+/// `String::from_utf8_lossy` would put it. Use `escape_ascii_bytes` to reject them instead.
+///
+/// The C1 controls and the noncharacters are replaced with `&#xFFFD;` too, since no reference
+/// decodes back to them and they cannot be passed through as ASCII.
+///
+/// # Usage
+///
+/// ```
+/// use marksman_escape::{EscapeAscii, EscapeContext};
+/// let string = b"<b>caf\xC3\xA9 \xF0\x9F\x98\x80 \xFF</b>";
+/// let escaped = EscapeAscii::with_context(string.iter().cloned(), EscapeContext::Text)
+///                           .collect::<Vec<_>>();
+/// assert_eq!(&*escaped, &b"&lt;b&gt;caf&#xE9; &#x1F600; &#xFFFD;&lt;/b&gt;"[..]);
+/// ```
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct EscapeAscii<'t, I: Iterator<Item=u8>> {
+    inner: I,
+    table: &'t EscapeTable<'t>,
+    /// Byte read from the inner iterator after an invalid UTF-8 sequence.
+    peeked: Option<u8>,
+    /// Rest of the replacement from the table being emitted.
+    pending: &'t [u8],
+    buffer: [u8; LONGEST_HEX_REFERENCE],
+    start: usize,
+    end: usize,
+}
+
+impl<'t, I: Iterator<Item=u8>> EscapeAscii<'t, I> {
+    /// Create an iterator adaptor which will escape all the bytes of internal iterator.
+    ///
+    /// The same ASCII characters as `Escape::new` escapes are replaced.
+    pub fn new(i: I) -> EscapeAscii<'t, I> {
+        EscapeAscii::with_table(i, EscapeTable::conservative())
+    }
+
+    /// Create an iterator adaptor which will escape the ASCII characters necessary for the given
+    /// context, in addition to the non-ASCII ones.
+    pub fn with_context(i: I, context: EscapeContext) -> EscapeAscii<'t, I> {
+        EscapeAscii::with_table(i, context.table())
+    }
+
+    /// Create an iterator adaptor which will escape the ASCII characters the table escapes, in
+    /// addition to the non-ASCII ones.
+    pub fn with_table(i: I, table: &'t EscapeTable<'t>) -> EscapeAscii<'t, I> {
+        EscapeAscii {
+            inner: i,
+            table,
+            peeked: None,
+            pending: &[],
+            buffer: [0; LONGEST_HEX_REFERENCE],
+            start: 0,
+            end: 0,
+        }
+    }
+}
+
+impl<'t, I: Iterator<Item=u8>> Iterator for EscapeAscii<'t, I> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if let Some((&first, rest)) = self.pending.split_first() {
+            self.pending = rest;
+            return Some(first);
+        }
+        if self.start < self.end {
+            let ret = self.buffer[self.start];
+            self.start += 1;
+            return Some(ret);
+        }
+        let ch = self.peeked.take().or_else(|| self.inner.next())?;
+        if ch < 0x80 {
+            self.pending = self.table.get(ch).as_bytes();
+            if self.pending.is_empty() {
+                return Some(ch);
+            }
+        } else {
+            let decoded = read_char(ch, &mut self.inner, &mut self.peeked).unwrap_or('\u{FFFD}');
+            self.start = 0;
+            self.end = write_hex_reference(replace_unescapable(decoded), &mut self.buffer);
+        }
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (l, u) = self.inner.size_hint();
+        let buffered = self.pending.len() + self.end - self.start;
+        let peeked = self.peeked.is_some() as usize;
+        // A non-ASCII byte grows to at most 8 bytes, the length of `&#xFFFD;` for an invalid byte.
+        let longest = self.table.longest().max("&#xFFFD;".len());
+        let upper = u.and_then(|u_| u_.checked_add(peeked))
+                     .and_then(|u_| u_.checked_mul(longest))
+                     .and_then(|u_| u_.checked_add(buffered));
+        (l.saturating_add(peeked).saturating_add(buffered), upper)
+    }
+}
+
+/// Escape a byte slice into pure 7-bit ASCII with the same table `Escape::new` uses.
+///
+/// Same as `EscapeAscii`, except that invalid UTF-8 is reported rather than replaced. The C1
+/// controls and the noncharacters are still replaced with `&#xFFFD;`. The slice is returned as is
+/// if it contains nothing to escape.
+///
+/// # Usage
+///
+/// ```
+/// use marksman_escape::escape_ascii_bytes;
+/// assert_eq!(&*escape_ascii_bytes(b"<caf\xC3\xA9>").unwrap(), b"&lt;caf&#xE9;&gt;");
+/// assert_eq!(escape_ascii_bytes(b"caf\xC3").unwrap_err().valid_up_to(), 3);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn escape_ascii_bytes<'a>(bytes: &'a [u8]) -> Result<Cow<'a, [u8]>, Utf8Error> {
+    Ok(match escape_ascii_str(str::from_utf8(bytes)?) {
+        Cow::Borrowed(_) => Cow::Borrowed(bytes),
+        Cow::Owned(s) => Cow::Owned(s.into_bytes())
+    })
+}
+
+/// Escape a string slice into pure 7-bit ASCII with the same table `Escape::new` uses.
+///
+/// The C1 controls and the noncharacters are replaced with `&#xFFFD;`, like `EscapeAscii` does.
+/// The slice is returned as is if it contains nothing to escape.
+///
+/// # Usage
+///
+/// ```
+/// use marksman_escape::escape_ascii_str;
+/// assert_eq!(escape_ascii_str("Zoë & Chloé"), "Zo&#xEB; &amp; Chlo&#xE9;");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn escape_ascii_str<'a>(string: &'a str) -> Cow<'a, str> {
+    let table = EscapeTable::conservative();
+    if string.is_ascii() {
+        return table.escape_str(string);
+    }
+    let len = string.chars().map(|ch| match ch {
+        '\0'..='\x7F' => table.get(ch as u8).len().max(1),
+        _ => hex_reference_len(replace_unescapable(ch))
+    }).sum();
+    let mut out = Vec::with_capacity(len);
+    let mut rest = string;
+    while let Some(i) = rest.bytes().position(|b| b >= 0x80) {
+        table.escape_to(&rest.as_bytes()[..i], &mut out);
+        let ch = rest[i..].chars().next().unwrap_or('\u{FFFD}');
+        let mut reference = [0; LONGEST_HEX_REFERENCE];
+        let len = write_hex_reference(replace_unescapable(ch), &mut reference);
+        out.extend_from_slice(&reference[..len]);
+        rest = &rest[i + ch.len_utf8()..];
+    }
+    table.escape_to(rest.as_bytes(), &mut out);
+    // Only ASCII bytes are written and all the replacements are valid UTF-8.
+    Cow::Owned(unsafe { String::from_utf8_unchecked(out) })
+}
+
+/// The character to write the reference to in place of `ch`, U+FFFD if no reference decodes back
+/// to `ch`.
+fn replace_unescapable(ch: char) -> char {
+    if survives_reference(ch) { ch } else { '\u{FFFD}' }
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+    use std::prelude::v1::*;
+    use ::{EscapeAscii, EscapeContext, Unescape, escape_ascii_bytes, escape_ascii_str};
+
+    fn run_test(from: &[u8], to: &str) {
+        let ev = EscapeAscii::with_context(from.iter().cloned(), EscapeContext::Text)
+                             .collect::<Vec<_>>();
+        assert_eq!(String::from_utf8(ev).unwrap(), to);
+    }
+
+    #[test]
+    fn escape_ascii(){
+        run_test(b"Hello world!", "Hello world!");
+        run_test("<\u{a0}\u{7ff}\u{800}\u{fffd}\u{10000}\u{10fffd}>".as_bytes(),
+                 "&lt;&#xA0;&#x7FF;&#x800;&#xFFFD;&#x10000;&#x10FFFD;&gt;");
+        run_test("\u{7f}\u{80}\u{9f}\u{fdd0}\u{fdef}\u{fffe}\u{ffff}\u{10ffff}".as_bytes(),
+                 "\u{7f}&#xFFFD;&#xFFFD;&#xFFFD;&#xFFFD;&#xFFFD;&#xFFFD;&#xFFFD;");
+        let ev = EscapeAscii::new("(\u{e9})".bytes()).collect::<Vec<_>>();
+        assert_eq!(String::from_utf8(ev).unwrap(), "&#40;&#xE9;&#41;");
+    }
+
+    #[test]
+    fn invalid_utf8(){
+        // Same replacements as `String::from_utf8_lossy`.
+        let inputs: &[&[u8]] = &[
+            b"\xC3", b"\xC3<", b"\x80\xBF", b"\xC0\xAF", b"\xE0\x80\x80", b"\xED\xA0\x80",
+            b"\xE2\x82", b"\xE2\x82<\xE2\x82\xAC", b"\xF0\x9F\x98", b"\xF4\x90\x80\x80", b"\xFF",
+            b"\xF0\x9F\x98\x80\xF0",
+        ];
+        for &input in inputs {
+            let lossy = String::from_utf8_lossy(input);
+            let expected = EscapeAscii::with_context(lossy.bytes(), EscapeContext::Text)
+                                       .collect::<Vec<_>>();
+            let found = EscapeAscii::with_context(input.iter().cloned(), EscapeContext::Text)
+                                    .collect::<Vec<_>>();
+            assert_eq!(found, expected);
+        }
+    }
+
+    #[test]
+    fn round_trip(){
+        let input = "<\u{7f}\u{a0}\u{fdcf}\u{fdf0}\u{fffd}\u{1D56B}\u{1F600}\u{10fffd}&amp;";
+        let escaped = EscapeAscii::new(input.bytes()).collect::<Vec<_>>();
+        assert!(escaped.is_ascii());
+        assert_eq!(Unescape::new(escaped.into_iter()).collect::<Vec<_>>(), input.as_bytes());
+        let escaped = escape_ascii_str(input);
+        assert_eq!(Unescape::new(escaped.bytes()).collect::<Vec<_>>(), input.as_bytes());
+    }
+
+    #[test]
+    fn slices(){
+        assert_eq!(escape_ascii_str("hello"), Cow::Borrowed("hello"));
+        assert_eq!(escape_ascii_str("<\u{e9}>\u{1F600}!"), "&lt;&#xE9;&gt;&#x1F600;&#33;");
+        let escaped = escape_ascii_str("\u{0}\u{f}\u{10}\u{ff}\u{100}\u{fff}\u{1000}\u{10ffff}")
+                                       .into_owned();
+        assert_eq!(escaped.len(), escaped.capacity());
+        assert_eq!(escape_ascii_str("\u{80}\u{fdd0}\u{10ffff}"), "&#xFFFD;&#xFFFD;&#xFFFD;");
+        assert_eq!(&*escape_ascii_bytes(b"hello").unwrap(), b"hello");
+        assert_eq!(&*escape_ascii_bytes(b"\xC3\xA9\xE2\x82\xAC").unwrap(), b"&#xE9;&#x20AC;");
+        assert_eq!(escape_ascii_bytes(b"ab\xED\xA0\x80").unwrap_err().valid_up_to(), 2);
+    }
+}
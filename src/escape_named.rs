@@ -0,0 +1,180 @@
+use escape::{EscapeContext, EscapeTable};
+use utf8::{LONGEST_HEX_REFERENCE, read_char, survives_reference, write_hex_reference};
+
+
+/// Escape a byte stream with the shortest HTML5 named references, for output meant to be read and
+/// edited by humans.
+///
+/// The bytes the table escapes and all the non-ASCII characters are replaced with their shortest
+/// [HTML5 named character reference][html5-nref], e.g. `&copy;` rather than `&#169;` and `&lpar;`
+/// rather than `&#40;`. Bytes without a name are replaced as the table says, and characters
+/// without a name with a hexadecimal reference such as `&#x1F600;`.
+///
+/// The implementation works with bytes interpreting them to be UTF-8. Bytes which are not valid
+/// UTF-8 are passed through unchanged, and so are the C1 controls and the noncharacters, which no
+/// reference decodes back to.
+///
+/// # Usage
+///
+/// ```
+/// use marksman_escape::{EscapeContext, EscapeNamed};
+/// let string = "<b>© 2015 (café) 😀</b>";
+/// let escaped = String::from_utf8(EscapeNamed::with_context(string.bytes(), EscapeContext::Text)
+///                                             .collect()).unwrap();
+/// assert_eq!("&lt;b&gt;&copy; 2015 (caf&eacute;) &#x1F600;&lt;/b&gt;", &*escaped);
+/// ```
+///
+/// [html5-nref]: http://www.w3.org/TR/html5/syntax.html#named-character-references
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct EscapeNamed<'t, I: Iterator<Item=u8>> {
+    inner: I,
+    table: &'t EscapeTable<'t>,
+    /// Byte read from the inner iterator after an incomplete UTF-8 sequence.
+    peeked: Option<u8>,
+    /// Rest of the name or the replacement from the table being emitted.
+    pending: &'t [u8],
+    buffer: [u8; LONGEST_HEX_REFERENCE],
+    start: usize,
+    end: usize,
+}
+
+impl<'t, I: Iterator<Item=u8>> EscapeNamed<'t, I> {
+    /// Create an iterator adaptor which will escape all the bytes of internal iterator.
+    ///
+    /// The same ASCII characters as `Escape::new` escapes are replaced.
+    pub fn new(i: I) -> EscapeNamed<'t, I> {
+        EscapeNamed::with_table(i, EscapeTable::conservative())
+    }
+
+    /// Create an iterator adaptor which will escape the ASCII characters necessary for the given
+    /// context, in addition to the non-ASCII ones.
+    pub fn with_context(i: I, context: EscapeContext) -> EscapeNamed<'t, I> {
+        EscapeNamed::with_table(i, context.table())
+    }
+
+    /// Create an iterator adaptor which will escape the ASCII characters the table escapes, in
+    /// addition to the non-ASCII ones.
+    pub fn with_table(i: I, table: &'t EscapeTable<'t>) -> EscapeNamed<'t, I> {
+        EscapeNamed {
+            inner: i,
+            table,
+            peeked: None,
+            pending: &[],
+            buffer: [0; LONGEST_HEX_REFERENCE],
+            start: 0,
+            end: 0,
+        }
+    }
+
+}
+
+impl<'t, I: Iterator<Item=u8>> Iterator for EscapeNamed<'t, I> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if let Some((&first, rest)) = self.pending.split_first() {
+            self.pending = rest;
+            return Some(first);
+        }
+        if self.start < self.end {
+            let ret = self.buffer[self.start];
+            self.start += 1;
+            return Some(ret);
+        }
+        let ch = self.peeked.take().or_else(|| self.inner.next())?;
+        if ch < 0x80 {
+            let escaped = self.table.get(ch);
+            if escaped.is_empty() {
+                return Some(ch);
+            }
+            self.pending = find_name(ch as char).unwrap_or(escaped).as_bytes();
+        } else {
+            match read_char(ch, &mut self.inner, &mut self.peeked) {
+                Ok(decoded) => match find_name(decoded) {
+                    Some(name) => self.pending = name.as_bytes(),
+                    None if survives_reference(decoded) => {
+                        self.start = 0;
+                        self.end = write_hex_reference(decoded, &mut self.buffer);
+                    },
+                    None => {
+                        self.start = 0;
+                        self.end = decoded.encode_utf8(&mut self.buffer).len();
+                    }
+                },
+                Err((sequence, len)) => {
+                    self.buffer[..len].copy_from_slice(&sequence[..len]);
+                    self.start = 0;
+                    self.end = len;
+                }
+            }
+        }
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (l, u) = self.inner.size_hint();
+        let buffered = self.pending.len() + self.end - self.start;
+        let peeked = self.peeked.is_some() as usize;
+        let longest = self.table.longest().max(LONGEST_ENCODED_REFERENCE);
+        let upper = u.and_then(|u_| u_.checked_add(peeked))
+                     .and_then(|u_| u_.checked_mul(longest))
+                     .and_then(|u_| u_.checked_add(buffered));
+        (l.saturating_add(peeked).saturating_add(buffered), upper)
+    }
+}
+
+/// The shortest named reference of the character, if it has any.
+fn find_name(ch: char) -> Option<&'static str> {
+    ENCODED.binary_search_by_key(&ch, |&(c, _)| c).ok().map(|i| ENCODED[i].1)
+}
+
+include!("escape_named_gen.rs");
+
+
+#[cfg(test)]
+mod test {
+    use std::prelude::v1::*;
+    use ::{EscapeContext, EscapeNamed, Unescape};
+    use test_util::check_size_hint;
+
+    fn run_test(context: EscapeContext, from: &[u8], to: &str) {
+        let ev = EscapeNamed::with_context(from.iter().cloned(), context).collect::<Vec<_>>();
+        assert_eq!(String::from_utf8(ev).unwrap(), to);
+    }
+
+    #[test]
+    fn escape_named(){
+        run_test(EscapeContext::Text, b"Hello world!", "Hello world!");
+        run_test(EscapeContext::Text, "<&>\u{a0}\u{a9}\u{c5}\u{a8}".as_bytes(),
+                 "&lt;&amp;&gt;&nbsp;&copy;&Aring;&uml;");
+        run_test(EscapeContext::Text, "\u{a1}\u{4e2d}\u{1F600}\u{1D56B}\u{25AB}".as_bytes(),
+                 "&iexcl;&#x4E2D;&#x1F600;&zopf;&EmptyVerySmallSquare;");
+        run_test(EscapeContext::Text, "\u{80}\u{9f}\u{fdd0}\u{fffe}\u{10ffff}".as_bytes(),
+                 "\u{80}\u{9f}\u{fdd0}\u{fffe}\u{10ffff}");
+        run_test(EscapeContext::UnquotedAttr, b"a=\"(b)\"\t\x0C `c'",
+                 "a&equals;&quot;(b)&quot;&Tab;&#12;&#32;&grave;c&apos;");
+        let ev = EscapeNamed::new("(@{x})".bytes()).collect::<Vec<_>>();
+        assert_eq!(String::from_utf8(ev).unwrap(), "&lpar;&commat;&lcub;x&rcub;&rpar;");
+    }
+
+    #[test]
+    fn invalid_utf8(){
+        let ev = EscapeNamed::new(b"\xC3<\xE2\x82\xFF\x80\xED\xA0\x80\xC3\xA9".iter().cloned())
+                                 .collect::<Vec<_>>();
+        assert_eq!(ev, &b"\xC3&lt;\xE2\x82\xFF\x80\xED\xA0\x80&eacute;"[..]);
+    }
+
+    #[test]
+    fn round_trip(){
+        let input = "<\u{7f}\u{80}\u{9f}\u{a0}\u{fdcf}\u{fdd0}\u{fdef}\u{fffd}\u{fffe}\u{1fffe}\
+                     \u{1D56B}\u{1F600}\u{10ffff}&amp;";
+        let escaped = EscapeNamed::new(input.bytes()).collect::<Vec<_>>();
+        assert_eq!(Unescape::new(escaped.into_iter()).collect::<Vec<_>>(), input.as_bytes());
+    }
+
+    #[test]
+    fn size_hint(){
+        let expected = "&lt;&EmptyVerySmallSquare;&copy;&#x1F600;".len();
+        check_size_hint(EscapeNamed::new("<\u{25AB}\u{a9}\u{1F600}".bytes()), expected);
+    }
+}
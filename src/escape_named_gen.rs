@@ -0,0 +1,369 @@
+// This file is autogenerated by generate.py, do not modify
+
+/// Length of the longest named reference in `ENCODED`, including the `&` and `;`.
+pub const LONGEST_ENCODED_REFERENCE : usize = 22;
+
+/// The shortest named reference of every character which has one, sorted by the character.
+pub static ENCODED: [(char, &str); 1446] = [
+('\u{9}', "&Tab;"), ('\u{A}', "&NewLine;"), ('\u{21}', "&excl;"), ('\u{22}', "&quot;"),
+('\u{23}', "&num;"), ('\u{24}', "&dollar;"), ('\u{25}', "&percnt;"), ('\u{26}', "&amp;"),
+('\u{27}', "&apos;"), ('\u{28}', "&lpar;"), ('\u{29}', "&rpar;"), ('\u{2A}', "&ast;"),
+('\u{2B}', "&plus;"), ('\u{2C}', "&comma;"), ('\u{2E}', "&period;"), ('\u{2F}', "&sol;"),
+('\u{3A}', "&colon;"), ('\u{3B}', "&semi;"), ('\u{3C}', "&lt;"), ('\u{3D}', "&equals;"),
+('\u{3E}', "&gt;"), ('\u{3F}', "&quest;"), ('\u{40}', "&commat;"), ('\u{5B}', "&lsqb;"),
+('\u{5C}', "&bsol;"), ('\u{5D}', "&rsqb;"), ('\u{5E}', "&Hat;"), ('\u{5F}', "&lowbar;"),
+('\u{60}', "&grave;"), ('\u{7B}', "&lcub;"), ('\u{7C}', "&vert;"), ('\u{7D}', "&rcub;"),
+('\u{A0}', "&nbsp;"), ('\u{A1}', "&iexcl;"), ('\u{A2}', "&cent;"), ('\u{A3}', "&pound;"),
+('\u{A4}', "&curren;"), ('\u{A5}', "&yen;"), ('\u{A6}', "&brvbar;"), ('\u{A7}', "&sect;"),
+('\u{A8}', "&uml;"), ('\u{A9}', "&copy;"), ('\u{AA}', "&ordf;"), ('\u{AB}', "&laquo;"),
+('\u{AC}', "&not;"), ('\u{AD}', "&shy;"), ('\u{AE}', "&reg;"), ('\u{AF}', "&macr;"),
+('\u{B0}', "&deg;"), ('\u{B1}', "&pm;"), ('\u{B2}', "&sup2;"), ('\u{B3}', "&sup3;"),
+('\u{B4}', "&acute;"), ('\u{B5}', "&micro;"), ('\u{B6}', "&para;"), ('\u{B7}', "&middot;"),
+('\u{B8}', "&cedil;"), ('\u{B9}', "&sup1;"), ('\u{BA}', "&ordm;"), ('\u{BB}', "&raquo;"),
+('\u{BC}', "&frac14;"), ('\u{BD}', "&half;"), ('\u{BE}', "&frac34;"), ('\u{BF}', "&iquest;"),
+('\u{C0}', "&Agrave;"), ('\u{C1}', "&Aacute;"), ('\u{C2}', "&Acirc;"), ('\u{C3}', "&Atilde;"),
+('\u{C4}', "&Auml;"), ('\u{C5}', "&Aring;"), ('\u{C6}', "&AElig;"), ('\u{C7}', "&Ccedil;"),
+('\u{C8}', "&Egrave;"), ('\u{C9}', "&Eacute;"), ('\u{CA}', "&Ecirc;"), ('\u{CB}', "&Euml;"),
+('\u{CC}', "&Igrave;"), ('\u{CD}', "&Iacute;"), ('\u{CE}', "&Icirc;"), ('\u{CF}', "&Iuml;"),
+('\u{D0}', "&ETH;"), ('\u{D1}', "&Ntilde;"), ('\u{D2}', "&Ograve;"), ('\u{D3}', "&Oacute;"),
+('\u{D4}', "&Ocirc;"), ('\u{D5}', "&Otilde;"), ('\u{D6}', "&Ouml;"), ('\u{D7}', "&times;"),
+('\u{D8}', "&Oslash;"), ('\u{D9}', "&Ugrave;"), ('\u{DA}', "&Uacute;"), ('\u{DB}', "&Ucirc;"),
+('\u{DC}', "&Uuml;"), ('\u{DD}', "&Yacute;"), ('\u{DE}', "&THORN;"), ('\u{DF}', "&szlig;"),
+('\u{E0}', "&agrave;"), ('\u{E1}', "&aacute;"), ('\u{E2}', "&acirc;"), ('\u{E3}', "&atilde;"),
+('\u{E4}', "&auml;"), ('\u{E5}', "&aring;"), ('\u{E6}', "&aelig;"), ('\u{E7}', "&ccedil;"),
+('\u{E8}', "&egrave;"), ('\u{E9}', "&eacute;"), ('\u{EA}', "&ecirc;"), ('\u{EB}', "&euml;"),
+('\u{EC}', "&igrave;"), ('\u{ED}', "&iacute;"), ('\u{EE}', "&icirc;"), ('\u{EF}', "&iuml;"),
+('\u{F0}', "&eth;"), ('\u{F1}', "&ntilde;"), ('\u{F2}', "&ograve;"), ('\u{F3}', "&oacute;"),
+('\u{F4}', "&ocirc;"), ('\u{F5}', "&otilde;"), ('\u{F6}', "&ouml;"), ('\u{F7}', "&div;"),
+('\u{F8}', "&oslash;"), ('\u{F9}', "&ugrave;"), ('\u{FA}', "&uacute;"), ('\u{FB}', "&ucirc;"),
+('\u{FC}', "&uuml;"), ('\u{FD}', "&yacute;"), ('\u{FE}', "&thorn;"), ('\u{FF}', "&yuml;"),
+('\u{100}', "&Amacr;"), ('\u{101}', "&amacr;"), ('\u{102}', "&Abreve;"), ('\u{103}', "&abreve;"),
+('\u{104}', "&Aogon;"), ('\u{105}', "&aogon;"), ('\u{106}', "&Cacute;"), ('\u{107}', "&cacute;"),
+('\u{108}', "&Ccirc;"), ('\u{109}', "&ccirc;"), ('\u{10A}', "&Cdot;"), ('\u{10B}', "&cdot;"),
+('\u{10C}', "&Ccaron;"), ('\u{10D}', "&ccaron;"), ('\u{10E}', "&Dcaron;"), ('\u{10F}', "&dcaron;"),
+('\u{110}', "&Dstrok;"), ('\u{111}', "&dstrok;"), ('\u{112}', "&Emacr;"), ('\u{113}', "&emacr;"),
+('\u{116}', "&Edot;"), ('\u{117}', "&edot;"), ('\u{118}', "&Eogon;"), ('\u{119}', "&eogon;"),
+('\u{11A}', "&Ecaron;"), ('\u{11B}', "&ecaron;"), ('\u{11C}', "&Gcirc;"), ('\u{11D}', "&gcirc;"),
+('\u{11E}', "&Gbreve;"), ('\u{11F}', "&gbreve;"), ('\u{120}', "&Gdot;"), ('\u{121}', "&gdot;"),
+('\u{122}', "&Gcedil;"), ('\u{124}', "&Hcirc;"), ('\u{125}', "&hcirc;"), ('\u{126}', "&Hstrok;"),
+('\u{127}', "&hstrok;"), ('\u{128}', "&Itilde;"), ('\u{129}', "&itilde;"), ('\u{12A}', "&Imacr;"),
+('\u{12B}', "&imacr;"), ('\u{12E}', "&Iogon;"), ('\u{12F}', "&iogon;"), ('\u{130}', "&Idot;"),
+('\u{131}', "&imath;"), ('\u{132}', "&IJlig;"), ('\u{133}', "&ijlig;"), ('\u{134}', "&Jcirc;"),
+('\u{135}', "&jcirc;"), ('\u{136}', "&Kcedil;"), ('\u{137}', "&kcedil;"), ('\u{138}', "&kgreen;"),
+('\u{139}', "&Lacute;"), ('\u{13A}', "&lacute;"), ('\u{13B}', "&Lcedil;"), ('\u{13C}', "&lcedil;"),
+('\u{13D}', "&Lcaron;"), ('\u{13E}', "&lcaron;"), ('\u{13F}', "&Lmidot;"), ('\u{140}', "&lmidot;"),
+('\u{141}', "&Lstrok;"), ('\u{142}', "&lstrok;"), ('\u{143}', "&Nacute;"), ('\u{144}', "&nacute;"),
+('\u{145}', "&Ncedil;"), ('\u{146}', "&ncedil;"), ('\u{147}', "&Ncaron;"), ('\u{148}', "&ncaron;"),
+('\u{149}', "&napos;"), ('\u{14A}', "&ENG;"), ('\u{14B}', "&eng;"), ('\u{14C}', "&Omacr;"),
+('\u{14D}', "&omacr;"), ('\u{150}', "&Odblac;"), ('\u{151}', "&odblac;"), ('\u{152}', "&OElig;"),
+('\u{153}', "&oelig;"), ('\u{154}', "&Racute;"), ('\u{155}', "&racute;"), ('\u{156}', "&Rcedil;"),
+('\u{157}', "&rcedil;"), ('\u{158}', "&Rcaron;"), ('\u{159}', "&rcaron;"), ('\u{15A}', "&Sacute;"),
+('\u{15B}', "&sacute;"), ('\u{15C}', "&Scirc;"), ('\u{15D}', "&scirc;"), ('\u{15E}', "&Scedil;"),
+('\u{15F}', "&scedil;"), ('\u{160}', "&Scaron;"), ('\u{161}', "&scaron;"), ('\u{162}', "&Tcedil;"),
+('\u{163}', "&tcedil;"), ('\u{164}', "&Tcaron;"), ('\u{165}', "&tcaron;"), ('\u{166}', "&Tstrok;"),
+('\u{167}', "&tstrok;"), ('\u{168}', "&Utilde;"), ('\u{169}', "&utilde;"), ('\u{16A}', "&Umacr;"),
+('\u{16B}', "&umacr;"), ('\u{16C}', "&Ubreve;"), ('\u{16D}', "&ubreve;"), ('\u{16E}', "&Uring;"),
+('\u{16F}', "&uring;"), ('\u{170}', "&Udblac;"), ('\u{171}', "&udblac;"), ('\u{172}', "&Uogon;"),
+('\u{173}', "&uogon;"), ('\u{174}', "&Wcirc;"), ('\u{175}', "&wcirc;"), ('\u{176}', "&Ycirc;"),
+('\u{177}', "&ycirc;"), ('\u{178}', "&Yuml;"), ('\u{179}', "&Zacute;"), ('\u{17A}', "&zacute;"),
+('\u{17B}', "&Zdot;"), ('\u{17C}', "&zdot;"), ('\u{17D}', "&Zcaron;"), ('\u{17E}', "&zcaron;"),
+('\u{192}', "&fnof;"), ('\u{1B5}', "&imped;"), ('\u{1F5}', "&gacute;"), ('\u{237}', "&jmath;"),
+('\u{2C6}', "&circ;"), ('\u{2C7}', "&caron;"), ('\u{2D8}', "&breve;"), ('\u{2D9}', "&dot;"),
+('\u{2DA}', "&ring;"), ('\u{2DB}', "&ogon;"), ('\u{2DC}', "&tilde;"), ('\u{2DD}', "&dblac;"),
+('\u{311}', "&DownBreve;"), ('\u{391}', "&Alpha;"), ('\u{392}', "&Beta;"), ('\u{393}', "&Gamma;"),
+('\u{394}', "&Delta;"), ('\u{395}', "&Epsilon;"), ('\u{396}', "&Zeta;"), ('\u{397}', "&Eta;"),
+('\u{398}', "&Theta;"), ('\u{399}', "&Iota;"), ('\u{39A}', "&Kappa;"), ('\u{39B}', "&Lambda;"),
+('\u{39C}', "&Mu;"), ('\u{39D}', "&Nu;"), ('\u{39E}', "&Xi;"), ('\u{39F}', "&Omicron;"),
+('\u{3A0}', "&Pi;"), ('\u{3A1}', "&Rho;"), ('\u{3A3}', "&Sigma;"), ('\u{3A4}', "&Tau;"),
+('\u{3A5}', "&Upsilon;"), ('\u{3A6}', "&Phi;"), ('\u{3A7}', "&Chi;"), ('\u{3A8}', "&Psi;"),
+('\u{3A9}', "&ohm;"), ('\u{3B1}', "&alpha;"), ('\u{3B2}', "&beta;"), ('\u{3B3}', "&gamma;"),
+('\u{3B4}', "&delta;"), ('\u{3B5}', "&epsi;"), ('\u{3B6}', "&zeta;"), ('\u{3B7}', "&eta;"),
+('\u{3B8}', "&theta;"), ('\u{3B9}', "&iota;"), ('\u{3BA}', "&kappa;"), ('\u{3BB}', "&lambda;"),
+('\u{3BC}', "&mu;"), ('\u{3BD}', "&nu;"), ('\u{3BE}', "&xi;"), ('\u{3BF}', "&omicron;"),
+('\u{3C0}', "&pi;"), ('\u{3C1}', "&rho;"), ('\u{3C2}', "&sigmaf;"), ('\u{3C3}', "&sigma;"),
+('\u{3C4}', "&tau;"), ('\u{3C5}', "&upsi;"), ('\u{3C6}', "&phi;"), ('\u{3C7}', "&chi;"),
+('\u{3C8}', "&psi;"), ('\u{3C9}', "&omega;"), ('\u{3D1}', "&thetav;"), ('\u{3D2}', "&Upsi;"),
+('\u{3D5}', "&phiv;"), ('\u{3D6}', "&piv;"), ('\u{3DC}', "&Gammad;"), ('\u{3DD}', "&gammad;"),
+('\u{3F0}', "&kappav;"), ('\u{3F1}', "&rhov;"), ('\u{3F5}', "&epsiv;"), ('\u{3F6}', "&bepsi;"),
+('\u{401}', "&IOcy;"), ('\u{402}', "&DJcy;"), ('\u{403}', "&GJcy;"), ('\u{404}', "&Jukcy;"),
+('\u{405}', "&DScy;"), ('\u{406}', "&Iukcy;"), ('\u{407}', "&YIcy;"), ('\u{408}', "&Jsercy;"),
+('\u{409}', "&LJcy;"), ('\u{40A}', "&NJcy;"), ('\u{40B}', "&TSHcy;"), ('\u{40C}', "&KJcy;"),
+('\u{40E}', "&Ubrcy;"), ('\u{40F}', "&DZcy;"), ('\u{410}', "&Acy;"), ('\u{411}', "&Bcy;"),
+('\u{412}', "&Vcy;"), ('\u{413}', "&Gcy;"), ('\u{414}', "&Dcy;"), ('\u{415}', "&IEcy;"),
+('\u{416}', "&ZHcy;"), ('\u{417}', "&Zcy;"), ('\u{418}', "&Icy;"), ('\u{419}', "&Jcy;"),
+('\u{41A}', "&Kcy;"), ('\u{41B}', "&Lcy;"), ('\u{41C}', "&Mcy;"), ('\u{41D}', "&Ncy;"),
+('\u{41E}', "&Ocy;"), ('\u{41F}', "&Pcy;"), ('\u{420}', "&Rcy;"), ('\u{421}', "&Scy;"),
+('\u{422}', "&Tcy;"), ('\u{423}', "&Ucy;"), ('\u{424}', "&Fcy;"), ('\u{425}', "&KHcy;"),
+('\u{426}', "&TScy;"), ('\u{427}', "&CHcy;"), ('\u{428}', "&SHcy;"), ('\u{429}', "&SHCHcy;"),
+('\u{42A}', "&HARDcy;"), ('\u{42B}', "&Ycy;"), ('\u{42C}', "&SOFTcy;"), ('\u{42D}', "&Ecy;"),
+('\u{42E}', "&YUcy;"), ('\u{42F}', "&YAcy;"), ('\u{430}', "&acy;"), ('\u{431}', "&bcy;"),
+('\u{432}', "&vcy;"), ('\u{433}', "&gcy;"), ('\u{434}', "&dcy;"), ('\u{435}', "&iecy;"),
+('\u{436}', "&zhcy;"), ('\u{437}', "&zcy;"), ('\u{438}', "&icy;"), ('\u{439}', "&jcy;"),
+('\u{43A}', "&kcy;"), ('\u{43B}', "&lcy;"), ('\u{43C}', "&mcy;"), ('\u{43D}', "&ncy;"),
+('\u{43E}', "&ocy;"), ('\u{43F}', "&pcy;"), ('\u{440}', "&rcy;"), ('\u{441}', "&scy;"),
+('\u{442}', "&tcy;"), ('\u{443}', "&ucy;"), ('\u{444}', "&fcy;"), ('\u{445}', "&khcy;"),
+('\u{446}', "&tscy;"), ('\u{447}', "&chcy;"), ('\u{448}', "&shcy;"), ('\u{449}', "&shchcy;"),
+('\u{44A}', "&hardcy;"), ('\u{44B}', "&ycy;"), ('\u{44C}', "&softcy;"), ('\u{44D}', "&ecy;"),
+('\u{44E}', "&yucy;"), ('\u{44F}', "&yacy;"), ('\u{451}', "&iocy;"), ('\u{452}', "&djcy;"),
+('\u{453}', "&gjcy;"), ('\u{454}', "&jukcy;"), ('\u{455}', "&dscy;"), ('\u{456}', "&iukcy;"),
+('\u{457}', "&yicy;"), ('\u{458}', "&jsercy;"), ('\u{459}', "&ljcy;"), ('\u{45A}', "&njcy;"),
+('\u{45B}', "&tshcy;"), ('\u{45C}', "&kjcy;"), ('\u{45E}', "&ubrcy;"), ('\u{45F}', "&dzcy;"),
+('\u{2002}', "&ensp;"), ('\u{2003}', "&emsp;"), ('\u{2004}', "&emsp13;"), ('\u{2005}', "&emsp14;"),
+('\u{2007}', "&numsp;"), ('\u{2008}', "&puncsp;"), ('\u{2009}', "&thinsp;"), ('\u{200A}', "&hairsp;"),
+('\u{200B}', "&ZeroWidthSpace;"), ('\u{200C}', "&zwnj;"), ('\u{200D}', "&zwj;"), ('\u{200E}', "&lrm;"),
+('\u{200F}', "&rlm;"), ('\u{2010}', "&dash;"), ('\u{2013}', "&ndash;"), ('\u{2014}', "&mdash;"),
+('\u{2015}', "&horbar;"), ('\u{2016}', "&Vert;"), ('\u{2018}', "&lsquo;"), ('\u{2019}', "&rsquo;"),
+('\u{201A}', "&sbquo;"), ('\u{201C}', "&ldquo;"), ('\u{201D}', "&rdquo;"), ('\u{201E}', "&bdquo;"),
+('\u{2020}', "&dagger;"), ('\u{2021}', "&Dagger;"), ('\u{2022}', "&bull;"), ('\u{2025}', "&nldr;"),
+('\u{2026}', "&mldr;"), ('\u{2030}', "&permil;"), ('\u{2031}', "&pertenk;"), ('\u{2032}', "&prime;"),
+('\u{2033}', "&Prime;"), ('\u{2034}', "&tprime;"), ('\u{2035}', "&bprime;"), ('\u{2039}', "&lsaquo;"),
+('\u{203A}', "&rsaquo;"), ('\u{203E}', "&oline;"), ('\u{2041}', "&caret;"), ('\u{2043}', "&hybull;"),
+('\u{2044}', "&frasl;"), ('\u{204F}', "&bsemi;"), ('\u{2057}', "&qprime;"), ('\u{205F}', "&MediumSpace;"),
+('\u{2060}', "&NoBreak;"), ('\u{2061}', "&af;"), ('\u{2062}', "&it;"), ('\u{2063}', "&ic;"),
+('\u{20AC}', "&euro;"), ('\u{20DB}', "&tdot;"), ('\u{20DC}', "&DotDot;"), ('\u{2102}', "&Copf;"),
+('\u{2105}', "&incare;"), ('\u{210A}', "&gscr;"), ('\u{210B}', "&Hscr;"), ('\u{210C}', "&Hfr;"),
+('\u{210D}', "&Hopf;"), ('\u{210E}', "&planckh;"), ('\u{210F}', "&hbar;"), ('\u{2110}', "&Iscr;"),
+('\u{2111}', "&Im;"), ('\u{2112}', "&Lscr;"), ('\u{2113}', "&ell;"), ('\u{2115}', "&Nopf;"),
+('\u{2116}', "&numero;"), ('\u{2117}', "&copysr;"), ('\u{2118}', "&wp;"), ('\u{2119}', "&Popf;"),
+('\u{211A}', "&Qopf;"), ('\u{211B}', "&Rscr;"), ('\u{211C}', "&Re;"), ('\u{211D}', "&Ropf;"),
+('\u{211E}', "&rx;"), ('\u{2122}', "&trade;"), ('\u{2124}', "&Zopf;"), ('\u{2127}', "&mho;"),
+('\u{2128}', "&Zfr;"), ('\u{2129}', "&iiota;"), ('\u{212C}', "&Bscr;"), ('\u{212D}', "&Cfr;"),
+('\u{212F}', "&escr;"), ('\u{2130}', "&Escr;"), ('\u{2131}', "&Fscr;"), ('\u{2133}', "&Mscr;"),
+('\u{2134}', "&oscr;"), ('\u{2135}', "&aleph;"), ('\u{2136}', "&beth;"), ('\u{2137}', "&gimel;"),
+('\u{2138}', "&daleth;"), ('\u{2145}', "&DD;"), ('\u{2146}', "&dd;"), ('\u{2147}', "&ee;"),
+('\u{2148}', "&ii;"), ('\u{2153}', "&frac13;"), ('\u{2154}', "&frac23;"), ('\u{2155}', "&frac15;"),
+('\u{2156}', "&frac25;"), ('\u{2157}', "&frac35;"), ('\u{2158}', "&frac45;"), ('\u{2159}', "&frac16;"),
+('\u{215A}', "&frac56;"), ('\u{215B}', "&frac18;"), ('\u{215C}', "&frac38;"), ('\u{215D}', "&frac58;"),
+('\u{215E}', "&frac78;"), ('\u{2190}', "&larr;"), ('\u{2191}', "&uarr;"), ('\u{2192}', "&rarr;"),
+('\u{2193}', "&darr;"), ('\u{2194}', "&harr;"), ('\u{2195}', "&varr;"), ('\u{2196}', "&nwarr;"),
+('\u{2197}', "&nearr;"), ('\u{2198}', "&searr;"), ('\u{2199}', "&swarr;"), ('\u{219A}', "&nlarr;"),
+('\u{219B}', "&nrarr;"), ('\u{219D}', "&rarrw;"), ('\u{219E}', "&Larr;"), ('\u{219F}', "&Uarr;"),
+('\u{21A0}', "&Rarr;"), ('\u{21A1}', "&Darr;"), ('\u{21A2}', "&larrtl;"), ('\u{21A3}', "&rarrtl;"),
+('\u{21A4}', "&mapstoleft;"), ('\u{21A5}', "&mapstoup;"), ('\u{21A6}', "&map;"), ('\u{21A7}', "&mapstodown;"),
+('\u{21A9}', "&larrhk;"), ('\u{21AA}', "&rarrhk;"), ('\u{21AB}', "&larrlp;"), ('\u{21AC}', "&rarrlp;"),
+('\u{21AD}', "&harrw;"), ('\u{21AE}', "&nharr;"), ('\u{21B0}', "&lsh;"), ('\u{21B1}', "&rsh;"),
+('\u{21B2}', "&ldsh;"), ('\u{21B3}', "&rdsh;"), ('\u{21B5}', "&crarr;"), ('\u{21B6}', "&cularr;"),
+('\u{21B7}', "&curarr;"), ('\u{21BA}', "&olarr;"), ('\u{21BB}', "&orarr;"), ('\u{21BC}', "&lharu;"),
+('\u{21BD}', "&lhard;"), ('\u{21BE}', "&uharr;"), ('\u{21BF}', "&uharl;"), ('\u{21C0}', "&rharu;"),
+('\u{21C1}', "&rhard;"), ('\u{21C2}', "&dharr;"), ('\u{21C3}', "&dharl;"), ('\u{21C4}', "&rlarr;"),
+('\u{21C5}', "&udarr;"), ('\u{21C6}', "&lrarr;"), ('\u{21C7}', "&llarr;"), ('\u{21C8}', "&uuarr;"),
+('\u{21C9}', "&rrarr;"), ('\u{21CA}', "&ddarr;"), ('\u{21CB}', "&lrhar;"), ('\u{21CC}', "&rlhar;"),
+('\u{21CD}', "&nlArr;"), ('\u{21CE}', "&nhArr;"), ('\u{21CF}', "&nrArr;"), ('\u{21D0}', "&lArr;"),
+('\u{21D1}', "&uArr;"), ('\u{21D2}', "&rArr;"), ('\u{21D3}', "&dArr;"), ('\u{21D4}', "&iff;"),
+('\u{21D5}', "&vArr;"), ('\u{21D6}', "&nwArr;"), ('\u{21D7}', "&neArr;"), ('\u{21D8}', "&seArr;"),
+('\u{21D9}', "&swArr;"), ('\u{21DA}', "&lAarr;"), ('\u{21DB}', "&rAarr;"), ('\u{21DD}', "&zigrarr;"),
+('\u{21E4}', "&larrb;"), ('\u{21E5}', "&rarrb;"), ('\u{21F5}', "&duarr;"), ('\u{21FD}', "&loarr;"),
+('\u{21FE}', "&roarr;"), ('\u{21FF}', "&hoarr;"), ('\u{2200}', "&forall;"), ('\u{2201}', "&comp;"),
+('\u{2202}', "&part;"), ('\u{2203}', "&exist;"), ('\u{2204}', "&nexist;"), ('\u{2205}', "&empty;"),
+('\u{2207}', "&Del;"), ('\u{2208}', "&in;"), ('\u{2209}', "&notin;"), ('\u{220B}', "&ni;"),
+('\u{220C}', "&notni;"), ('\u{220F}', "&prod;"), ('\u{2210}', "&coprod;"), ('\u{2211}', "&sum;"),
+('\u{2212}', "&minus;"), ('\u{2213}', "&mp;"), ('\u{2214}', "&plusdo;"), ('\u{2216}', "&setmn;"),
+('\u{2217}', "&lowast;"), ('\u{2218}', "&compfn;"), ('\u{221A}', "&Sqrt;"), ('\u{221D}', "&prop;"),
+('\u{221E}', "&infin;"), ('\u{221F}', "&angrt;"), ('\u{2220}', "&ang;"), ('\u{2221}', "&angmsd;"),
+('\u{2222}', "&angsph;"), ('\u{2223}', "&mid;"), ('\u{2224}', "&nmid;"), ('\u{2225}', "&par;"),
+('\u{2226}', "&npar;"), ('\u{2227}', "&and;"), ('\u{2228}', "&or;"), ('\u{2229}', "&cap;"),
+('\u{222A}', "&cup;"), ('\u{222B}', "&int;"), ('\u{222C}', "&Int;"), ('\u{222D}', "&tint;"),
+('\u{222E}', "&oint;"), ('\u{222F}', "&Conint;"), ('\u{2230}', "&Cconint;"), ('\u{2231}', "&cwint;"),
+('\u{2232}', "&cwconint;"), ('\u{2233}', "&awconint;"), ('\u{2234}', "&there4;"), ('\u{2235}', "&becaus;"),
+('\u{2236}', "&ratio;"), ('\u{2237}', "&Colon;"), ('\u{2238}', "&minusd;"), ('\u{223A}', "&mDDot;"),
+('\u{223B}', "&homtht;"), ('\u{223C}', "&sim;"), ('\u{223D}', "&bsim;"), ('\u{223E}', "&ac;"),
+('\u{223F}', "&acd;"), ('\u{2240}', "&wr;"), ('\u{2241}', "&nsim;"), ('\u{2242}', "&esim;"),
+('\u{2243}', "&sime;"), ('\u{2244}', "&nsime;"), ('\u{2245}', "&cong;"), ('\u{2246}', "&simne;"),
+('\u{2247}', "&ncong;"), ('\u{2248}', "&ap;"), ('\u{2249}', "&nap;"), ('\u{224A}', "&ape;"),
+('\u{224B}', "&apid;"), ('\u{224C}', "&bcong;"), ('\u{224D}', "&CupCap;"), ('\u{224E}', "&bump;"),
+('\u{224F}', "&bumpe;"), ('\u{2250}', "&doteq;"), ('\u{2251}', "&eDot;"), ('\u{2252}', "&efDot;"),
+('\u{2253}', "&erDot;"), ('\u{2254}', "&colone;"), ('\u{2255}', "&ecolon;"), ('\u{2256}', "&ecir;"),
+('\u{2257}', "&cire;"), ('\u{2259}', "&wedgeq;"), ('\u{225A}', "&veeeq;"), ('\u{225C}', "&trie;"),
+('\u{225F}', "&equest;"), ('\u{2260}', "&ne;"), ('\u{2261}', "&equiv;"), ('\u{2262}', "&nequiv;"),
+('\u{2264}', "&le;"), ('\u{2265}', "&ge;"), ('\u{2266}', "&lE;"), ('\u{2267}', "&gE;"),
+('\u{2268}', "&lnE;"), ('\u{2269}', "&gnE;"), ('\u{226A}', "&ll;"), ('\u{226B}', "&gg;"),
+('\u{226C}', "&twixt;"), ('\u{226D}', "&NotCupCap;"), ('\u{226E}', "&nlt;"), ('\u{226F}', "&ngt;"),
+('\u{2270}', "&nle;"), ('\u{2271}', "&nge;"), ('\u{2272}', "&lsim;"), ('\u{2273}', "&gsim;"),
+('\u{2274}', "&nlsim;"), ('\u{2275}', "&ngsim;"), ('\u{2276}', "&lg;"), ('\u{2277}', "&gl;"),
+('\u{2278}', "&ntlg;"), ('\u{2279}', "&ntgl;"), ('\u{227A}', "&pr;"), ('\u{227B}', "&sc;"),
+('\u{227C}', "&prcue;"), ('\u{227D}', "&sccue;"), ('\u{227E}', "&prsim;"), ('\u{227F}', "&scsim;"),
+('\u{2280}', "&npr;"), ('\u{2281}', "&nsc;"), ('\u{2282}', "&sub;"), ('\u{2283}', "&sup;"),
+('\u{2284}', "&nsub;"), ('\u{2285}', "&nsup;"), ('\u{2286}', "&sube;"), ('\u{2287}', "&supe;"),
+('\u{2288}', "&nsube;"), ('\u{2289}', "&nsupe;"), ('\u{228A}', "&subne;"), ('\u{228B}', "&supne;"),
+('\u{228D}', "&cupdot;"), ('\u{228E}', "&uplus;"), ('\u{228F}', "&sqsub;"), ('\u{2290}', "&sqsup;"),
+('\u{2291}', "&sqsube;"), ('\u{2292}', "&sqsupe;"), ('\u{2293}', "&sqcap;"), ('\u{2294}', "&sqcup;"),
+('\u{2295}', "&oplus;"), ('\u{2296}', "&ominus;"), ('\u{2297}', "&otimes;"), ('\u{2298}', "&osol;"),
+('\u{2299}', "&odot;"), ('\u{229A}', "&ocir;"), ('\u{229B}', "&oast;"), ('\u{229D}', "&odash;"),
+('\u{229E}', "&plusb;"), ('\u{229F}', "&minusb;"), ('\u{22A0}', "&timesb;"), ('\u{22A1}', "&sdotb;"),
+('\u{22A2}', "&vdash;"), ('\u{22A3}', "&dashv;"), ('\u{22A4}', "&top;"), ('\u{22A5}', "&bot;"),
+('\u{22A7}', "&models;"), ('\u{22A8}', "&vDash;"), ('\u{22A9}', "&Vdash;"), ('\u{22AA}', "&Vvdash;"),
+('\u{22AB}', "&VDash;"), ('\u{22AC}', "&nvdash;"), ('\u{22AD}', "&nvDash;"), ('\u{22AE}', "&nVdash;"),
+('\u{22AF}', "&nVDash;"), ('\u{22B0}', "&prurel;"), ('\u{22B2}', "&vltri;"), ('\u{22B3}', "&vrtri;"),
+('\u{22B4}', "&ltrie;"), ('\u{22B5}', "&rtrie;"), ('\u{22B6}', "&origof;"), ('\u{22B7}', "&imof;"),
+('\u{22B8}', "&mumap;"), ('\u{22B9}', "&hercon;"), ('\u{22BA}', "&intcal;"), ('\u{22BB}', "&veebar;"),
+('\u{22BD}', "&barvee;"), ('\u{22BE}', "&angrtvb;"), ('\u{22BF}', "&lrtri;"), ('\u{22C0}', "&Wedge;"),
+('\u{22C1}', "&Vee;"), ('\u{22C2}', "&xcap;"), ('\u{22C3}', "&xcup;"), ('\u{22C4}', "&diam;"),
+('\u{22C5}', "&sdot;"), ('\u{22C6}', "&Star;"), ('\u{22C7}', "&divonx;"), ('\u{22C8}', "&bowtie;"),
+('\u{22C9}', "&ltimes;"), ('\u{22CA}', "&rtimes;"), ('\u{22CB}', "&lthree;"), ('\u{22CC}', "&rthree;"),
+('\u{22CD}', "&bsime;"), ('\u{22CE}', "&cuvee;"), ('\u{22CF}', "&cuwed;"), ('\u{22D0}', "&Sub;"),
+('\u{22D1}', "&Sup;"), ('\u{22D2}', "&Cap;"), ('\u{22D3}', "&Cup;"), ('\u{22D4}', "&fork;"),
+('\u{22D5}', "&epar;"), ('\u{22D6}', "&ltdot;"), ('\u{22D7}', "&gtdot;"), ('\u{22D8}', "&Ll;"),
+('\u{22D9}', "&Gg;"), ('\u{22DA}', "&leg;"), ('\u{22DB}', "&gel;"), ('\u{22DE}', "&cuepr;"),
+('\u{22DF}', "&cuesc;"), ('\u{22E0}', "&nprcue;"), ('\u{22E1}', "&nsccue;"), ('\u{22E2}', "&nsqsube;"),
+('\u{22E3}', "&nsqsupe;"), ('\u{22E6}', "&lnsim;"), ('\u{22E7}', "&gnsim;"), ('\u{22E8}', "&prnsim;"),
+('\u{22E9}', "&scnsim;"), ('\u{22EA}', "&nltri;"), ('\u{22EB}', "&nrtri;"), ('\u{22EC}', "&nltrie;"),
+('\u{22ED}', "&nrtrie;"), ('\u{22EE}', "&vellip;"), ('\u{22EF}', "&ctdot;"), ('\u{22F0}', "&utdot;"),
+('\u{22F1}', "&dtdot;"), ('\u{22F2}', "&disin;"), ('\u{22F3}', "&isinsv;"), ('\u{22F4}', "&isins;"),
+('\u{22F5}', "&isindot;"), ('\u{22F6}', "&notinvc;"), ('\u{22F7}', "&notinvb;"), ('\u{22F9}', "&isinE;"),
+('\u{22FA}', "&nisd;"), ('\u{22FB}', "&xnis;"), ('\u{22FC}', "&nis;"), ('\u{22FD}', "&notnivc;"),
+('\u{22FE}', "&notnivb;"), ('\u{2305}', "&barwed;"), ('\u{2306}', "&Barwed;"), ('\u{2308}', "&lceil;"),
+('\u{2309}', "&rceil;"), ('\u{230A}', "&lfloor;"), ('\u{230B}', "&rfloor;"), ('\u{230C}', "&drcrop;"),
+('\u{230D}', "&dlcrop;"), ('\u{230E}', "&urcrop;"), ('\u{230F}', "&ulcrop;"), ('\u{2310}', "&bnot;"),
+('\u{2312}', "&profline;"), ('\u{2313}', "&profsurf;"), ('\u{2315}', "&telrec;"), ('\u{2316}', "&target;"),
+('\u{231C}', "&ulcorn;"), ('\u{231D}', "&urcorn;"), ('\u{231E}', "&dlcorn;"), ('\u{231F}', "&drcorn;"),
+('\u{2322}', "&frown;"), ('\u{2323}', "&smile;"), ('\u{232D}', "&cylcty;"), ('\u{232E}', "&profalar;"),
+('\u{2336}', "&topbot;"), ('\u{233D}', "&ovbar;"), ('\u{233F}', "&solbar;"), ('\u{237C}', "&angzarr;"),
+('\u{23B0}', "&lmoust;"), ('\u{23B1}', "&rmoust;"), ('\u{23B4}', "&tbrk;"), ('\u{23B5}', "&bbrk;"),
+('\u{23B6}', "&bbrktbrk;"), ('\u{23DC}', "&OverParenthesis;"), ('\u{23DD}', "&UnderParenthesis;"), ('\u{23DE}', "&OverBrace;"),
+('\u{23DF}', "&UnderBrace;"), ('\u{23E2}', "&trpezium;"), ('\u{23E7}', "&elinters;"), ('\u{2423}', "&blank;"),
+('\u{24C8}', "&oS;"), ('\u{2500}', "&boxh;"), ('\u{2502}', "&boxv;"), ('\u{250C}', "&boxdr;"),
+('\u{2510}', "&boxdl;"), ('\u{2514}', "&boxur;"), ('\u{2518}', "&boxul;"), ('\u{251C}', "&boxvr;"),
+('\u{2524}', "&boxvl;"), ('\u{252C}', "&boxhd;"), ('\u{2534}', "&boxhu;"), ('\u{253C}', "&boxvh;"),
+('\u{2550}', "&boxH;"), ('\u{2551}', "&boxV;"), ('\u{2552}', "&boxdR;"), ('\u{2553}', "&boxDr;"),
+('\u{2554}', "&boxDR;"), ('\u{2555}', "&boxdL;"), ('\u{2556}', "&boxDl;"), ('\u{2557}', "&boxDL;"),
+('\u{2558}', "&boxuR;"), ('\u{2559}', "&boxUr;"), ('\u{255A}', "&boxUR;"), ('\u{255B}', "&boxuL;"),
+('\u{255C}', "&boxUl;"), ('\u{255D}', "&boxUL;"), ('\u{255E}', "&boxvR;"), ('\u{255F}', "&boxVr;"),
+('\u{2560}', "&boxVR;"), ('\u{2561}', "&boxvL;"), ('\u{2562}', "&boxVl;"), ('\u{2563}', "&boxVL;"),
+('\u{2564}', "&boxHd;"), ('\u{2565}', "&boxhD;"), ('\u{2566}', "&boxHD;"), ('\u{2567}', "&boxHu;"),
+('\u{2568}', "&boxhU;"), ('\u{2569}', "&boxHU;"), ('\u{256A}', "&boxvH;"), ('\u{256B}', "&boxVh;"),
+('\u{256C}', "&boxVH;"), ('\u{2580}', "&uhblk;"), ('\u{2584}', "&lhblk;"), ('\u{2588}', "&block;"),
+('\u{2591}', "&blk14;"), ('\u{2592}', "&blk12;"), ('\u{2593}', "&blk34;"), ('\u{25A1}', "&squ;"),
+('\u{25AA}', "&squf;"), ('\u{25AB}', "&EmptyVerySmallSquare;"), ('\u{25AD}', "&rect;"), ('\u{25AE}', "&marker;"),
+('\u{25B1}', "&fltns;"), ('\u{25B3}', "&xutri;"), ('\u{25B4}', "&utrif;"), ('\u{25B5}', "&utri;"),
+('\u{25B8}', "&rtrif;"), ('\u{25B9}', "&rtri;"), ('\u{25BD}', "&xdtri;"), ('\u{25BE}', "&dtrif;"),
+('\u{25BF}', "&dtri;"), ('\u{25C2}', "&ltrif;"), ('\u{25C3}', "&ltri;"), ('\u{25CA}', "&loz;"),
+('\u{25CB}', "&cir;"), ('\u{25EC}', "&tridot;"), ('\u{25EF}', "&xcirc;"), ('\u{25F8}', "&ultri;"),
+('\u{25F9}', "&urtri;"), ('\u{25FA}', "&lltri;"), ('\u{25FB}', "&EmptySmallSquare;"), ('\u{25FC}', "&FilledSmallSquare;"),
+('\u{2605}', "&starf;"), ('\u{2606}', "&star;"), ('\u{260E}', "&phone;"), ('\u{2640}', "&female;"),
+('\u{2642}', "&male;"), ('\u{2660}', "&spades;"), ('\u{2663}', "&clubs;"), ('\u{2665}', "&hearts;"),
+('\u{2666}', "&diams;"), ('\u{266A}', "&sung;"), ('\u{266D}', "&flat;"), ('\u{266E}', "&natur;"),
+('\u{266F}', "&sharp;"), ('\u{2713}', "&check;"), ('\u{2717}', "&cross;"), ('\u{2720}', "&malt;"),
+('\u{2736}', "&sext;"), ('\u{2758}', "&VerticalSeparator;"), ('\u{2772}', "&lbbrk;"), ('\u{2773}', "&rbbrk;"),
+('\u{27C8}', "&bsolhsub;"), ('\u{27C9}', "&suphsol;"), ('\u{27E6}', "&lobrk;"), ('\u{27E7}', "&robrk;"),
+('\u{27E8}', "&lang;"), ('\u{27E9}', "&rang;"), ('\u{27EA}', "&Lang;"), ('\u{27EB}', "&Rang;"),
+('\u{27EC}', "&loang;"), ('\u{27ED}', "&roang;"), ('\u{27F5}', "&xlarr;"), ('\u{27F6}', "&xrarr;"),
+('\u{27F7}', "&xharr;"), ('\u{27F8}', "&xlArr;"), ('\u{27F9}', "&xrArr;"), ('\u{27FA}', "&xhArr;"),
+('\u{27FC}', "&xmap;"), ('\u{27FF}', "&dzigrarr;"), ('\u{2902}', "&nvlArr;"), ('\u{2903}', "&nvrArr;"),
+('\u{2904}', "&nvHarr;"), ('\u{2905}', "&Map;"), ('\u{290C}', "&lbarr;"), ('\u{290D}', "&rbarr;"),
+('\u{290E}', "&lBarr;"), ('\u{290F}', "&rBarr;"), ('\u{2910}', "&RBarr;"), ('\u{2911}', "&DDotrahd;"),
+('\u{2912}', "&UpArrowBar;"), ('\u{2913}', "&DownArrowBar;"), ('\u{2916}', "&Rarrtl;"), ('\u{2919}', "&latail;"),
+('\u{291A}', "&ratail;"), ('\u{291B}', "&lAtail;"), ('\u{291C}', "&rAtail;"), ('\u{291D}', "&larrfs;"),
+('\u{291E}', "&rarrfs;"), ('\u{291F}', "&larrbfs;"), ('\u{2920}', "&rarrbfs;"), ('\u{2923}', "&nwarhk;"),
+('\u{2924}', "&nearhk;"), ('\u{2925}', "&searhk;"), ('\u{2926}', "&swarhk;"), ('\u{2927}', "&nwnear;"),
+('\u{2928}', "&toea;"), ('\u{2929}', "&tosa;"), ('\u{292A}', "&swnwar;"), ('\u{2933}', "&rarrc;"),
+('\u{2935}', "&cudarrr;"), ('\u{2936}', "&ldca;"), ('\u{2937}', "&rdca;"), ('\u{2938}', "&cudarrl;"),
+('\u{2939}', "&larrpl;"), ('\u{293C}', "&curarrm;"), ('\u{293D}', "&cularrp;"), ('\u{2945}', "&rarrpl;"),
+('\u{2948}', "&harrcir;"), ('\u{2949}', "&Uarrocir;"), ('\u{294A}', "&lurdshar;"), ('\u{294B}', "&ldrushar;"),
+('\u{294E}', "&LeftRightVector;"), ('\u{294F}', "&RightUpDownVector;"), ('\u{2950}', "&DownLeftRightVector;"), ('\u{2951}', "&LeftUpDownVector;"),
+('\u{2952}', "&LeftVectorBar;"), ('\u{2953}', "&RightVectorBar;"), ('\u{2954}', "&RightUpVectorBar;"), ('\u{2955}', "&RightDownVectorBar;"),
+('\u{2956}', "&DownLeftVectorBar;"), ('\u{2957}', "&DownRightVectorBar;"), ('\u{2958}', "&LeftUpVectorBar;"), ('\u{2959}', "&LeftDownVectorBar;"),
+('\u{295A}', "&LeftTeeVector;"), ('\u{295B}', "&RightTeeVector;"), ('\u{295C}', "&RightUpTeeVector;"), ('\u{295D}', "&RightDownTeeVector;"),
+('\u{295E}', "&DownLeftTeeVector;"), ('\u{295F}', "&DownRightTeeVector;"), ('\u{2960}', "&LeftUpTeeVector;"), ('\u{2961}', "&LeftDownTeeVector;"),
+('\u{2962}', "&lHar;"), ('\u{2963}', "&uHar;"), ('\u{2964}', "&rHar;"), ('\u{2965}', "&dHar;"),
+('\u{2966}', "&luruhar;"), ('\u{2967}', "&ldrdhar;"), ('\u{2968}', "&ruluhar;"), ('\u{2969}', "&rdldhar;"),
+('\u{296A}', "&lharul;"), ('\u{296B}', "&llhard;"), ('\u{296C}', "&rharul;"), ('\u{296D}', "&lrhard;"),
+('\u{296E}', "&udhar;"), ('\u{296F}', "&duhar;"), ('\u{2970}', "&RoundImplies;"), ('\u{2971}', "&erarr;"),
+('\u{2972}', "&simrarr;"), ('\u{2973}', "&larrsim;"), ('\u{2974}', "&rarrsim;"), ('\u{2975}', "&rarrap;"),
+('\u{2976}', "&ltlarr;"), ('\u{2978}', "&gtrarr;"), ('\u{2979}', "&subrarr;"), ('\u{297B}', "&suplarr;"),
+('\u{297C}', "&lfisht;"), ('\u{297D}', "&rfisht;"), ('\u{297E}', "&ufisht;"), ('\u{297F}', "&dfisht;"),
+('\u{2985}', "&lopar;"), ('\u{2986}', "&ropar;"), ('\u{298B}', "&lbrke;"), ('\u{298C}', "&rbrke;"),
+('\u{298D}', "&lbrkslu;"), ('\u{298E}', "&rbrksld;"), ('\u{298F}', "&lbrksld;"), ('\u{2990}', "&rbrkslu;"),
+('\u{2991}', "&langd;"), ('\u{2992}', "&rangd;"), ('\u{2993}', "&lparlt;"), ('\u{2994}', "&rpargt;"),
+('\u{2995}', "&gtlPar;"), ('\u{2996}', "&ltrPar;"), ('\u{299A}', "&vzigzag;"), ('\u{299C}', "&vangrt;"),
+('\u{299D}', "&angrtvbd;"), ('\u{29A4}', "&ange;"), ('\u{29A5}', "&range;"), ('\u{29A6}', "&dwangle;"),
+('\u{29A7}', "&uwangle;"), ('\u{29A8}', "&angmsdaa;"), ('\u{29A9}', "&angmsdab;"), ('\u{29AA}', "&angmsdac;"),
+('\u{29AB}', "&angmsdad;"), ('\u{29AC}', "&angmsdae;"), ('\u{29AD}', "&angmsdaf;"), ('\u{29AE}', "&angmsdag;"),
+('\u{29AF}', "&angmsdah;"), ('\u{29B0}', "&bemptyv;"), ('\u{29B1}', "&demptyv;"), ('\u{29B2}', "&cemptyv;"),
+('\u{29B3}', "&raemptyv;"), ('\u{29B4}', "&laemptyv;"), ('\u{29B5}', "&ohbar;"), ('\u{29B6}', "&omid;"),
+('\u{29B7}', "&opar;"), ('\u{29B9}', "&operp;"), ('\u{29BB}', "&olcross;"), ('\u{29BC}', "&odsold;"),
+('\u{29BE}', "&olcir;"), ('\u{29BF}', "&ofcir;"), ('\u{29C0}', "&olt;"), ('\u{29C1}', "&ogt;"),
+('\u{29C2}', "&cirscir;"), ('\u{29C3}', "&cirE;"), ('\u{29C4}', "&solb;"), ('\u{29C5}', "&bsolb;"),
+('\u{29C9}', "&boxbox;"), ('\u{29CD}', "&trisb;"), ('\u{29CE}', "&rtriltri;"), ('\u{29CF}', "&LeftTriangleBar;"),
+('\u{29D0}', "&RightTriangleBar;"), ('\u{29DC}', "&iinfin;"), ('\u{29DD}', "&infintie;"), ('\u{29DE}', "&nvinfin;"),
+('\u{29E3}', "&eparsl;"), ('\u{29E4}', "&smeparsl;"), ('\u{29E5}', "&eqvparsl;"), ('\u{29EB}', "&lozf;"),
+('\u{29F4}', "&RuleDelayed;"), ('\u{29F6}', "&dsol;"), ('\u{2A00}', "&xodot;"), ('\u{2A01}', "&xoplus;"),
+('\u{2A02}', "&xotime;"), ('\u{2A04}', "&xuplus;"), ('\u{2A06}', "&xsqcup;"), ('\u{2A0C}', "&qint;"),
+('\u{2A0D}', "&fpartint;"), ('\u{2A10}', "&cirfnint;"), ('\u{2A11}', "&awint;"), ('\u{2A12}', "&rppolint;"),
+('\u{2A13}', "&scpolint;"), ('\u{2A14}', "&npolint;"), ('\u{2A15}', "&pointint;"), ('\u{2A16}', "&quatint;"),
+('\u{2A17}', "&intlarhk;"), ('\u{2A22}', "&pluscir;"), ('\u{2A23}', "&plusacir;"), ('\u{2A24}', "&simplus;"),
+('\u{2A25}', "&plusdu;"), ('\u{2A26}', "&plussim;"), ('\u{2A27}', "&plustwo;"), ('\u{2A29}', "&mcomma;"),
+('\u{2A2A}', "&minusdu;"), ('\u{2A2D}', "&loplus;"), ('\u{2A2E}', "&roplus;"), ('\u{2A2F}', "&Cross;"),
+('\u{2A30}', "&timesd;"), ('\u{2A31}', "&timesbar;"), ('\u{2A33}', "&smashp;"), ('\u{2A34}', "&lotimes;"),
+('\u{2A35}', "&rotimes;"), ('\u{2A36}', "&otimesas;"), ('\u{2A37}', "&Otimes;"), ('\u{2A38}', "&odiv;"),
+('\u{2A39}', "&triplus;"), ('\u{2A3A}', "&triminus;"), ('\u{2A3B}', "&tritime;"), ('\u{2A3C}', "&iprod;"),
+('\u{2A3F}', "&amalg;"), ('\u{2A40}', "&capdot;"), ('\u{2A42}', "&ncup;"), ('\u{2A43}', "&ncap;"),
+('\u{2A44}', "&capand;"), ('\u{2A45}', "&cupor;"), ('\u{2A46}', "&cupcap;"), ('\u{2A47}', "&capcup;"),
+('\u{2A48}', "&cupbrcap;"), ('\u{2A49}', "&capbrcup;"), ('\u{2A4A}', "&cupcup;"), ('\u{2A4B}', "&capcap;"),
+('\u{2A4C}', "&ccups;"), ('\u{2A4D}', "&ccaps;"), ('\u{2A50}', "&ccupssm;"), ('\u{2A53}', "&And;"),
+('\u{2A54}', "&Or;"), ('\u{2A55}', "&andand;"), ('\u{2A56}', "&oror;"), ('\u{2A57}', "&orslope;"),
+('\u{2A58}', "&andslope;"), ('\u{2A5A}', "&andv;"), ('\u{2A5B}', "&orv;"), ('\u{2A5C}', "&andd;"),
+('\u{2A5D}', "&ord;"), ('\u{2A5F}', "&wedbar;"), ('\u{2A66}', "&sdote;"), ('\u{2A6A}', "&simdot;"),
+('\u{2A6D}', "&congdot;"), ('\u{2A6E}', "&easter;"), ('\u{2A6F}', "&apacir;"), ('\u{2A70}', "&apE;"),
+('\u{2A71}', "&eplus;"), ('\u{2A72}', "&pluse;"), ('\u{2A73}', "&Esim;"), ('\u{2A74}', "&Colone;"),
+('\u{2A75}', "&Equal;"), ('\u{2A77}', "&eDDot;"), ('\u{2A78}', "&equivDD;"), ('\u{2A79}', "&ltcir;"),
+('\u{2A7A}', "&gtcir;"), ('\u{2A7B}', "&ltquest;"), ('\u{2A7C}', "&gtquest;"), ('\u{2A7D}', "&les;"),
+('\u{2A7E}', "&ges;"), ('\u{2A7F}', "&lesdot;"), ('\u{2A80}', "&gesdot;"), ('\u{2A81}', "&lesdoto;"),
+('\u{2A82}', "&gesdoto;"), ('\u{2A83}', "&lesdotor;"), ('\u{2A84}', "&gesdotol;"), ('\u{2A85}', "&lap;"),
+('\u{2A86}', "&gap;"), ('\u{2A87}', "&lne;"), ('\u{2A88}', "&gne;"), ('\u{2A89}', "&lnap;"),
+('\u{2A8A}', "&gnap;"), ('\u{2A8B}', "&lEg;"), ('\u{2A8C}', "&gEl;"), ('\u{2A8D}', "&lsime;"),
+('\u{2A8E}', "&gsime;"), ('\u{2A8F}', "&lsimg;"), ('\u{2A90}', "&gsiml;"), ('\u{2A91}', "&lgE;"),
+('\u{2A92}', "&glE;"), ('\u{2A93}', "&lesges;"), ('\u{2A94}', "&gesles;"), ('\u{2A95}', "&els;"),
+('\u{2A96}', "&egs;"), ('\u{2A97}', "&elsdot;"), ('\u{2A98}', "&egsdot;"), ('\u{2A99}', "&el;"),
+('\u{2A9A}', "&eg;"), ('\u{2A9D}', "&siml;"), ('\u{2A9E}', "&simg;"), ('\u{2A9F}', "&simlE;"),
+('\u{2AA0}', "&simgE;"), ('\u{2AA1}', "&LessLess;"), ('\u{2AA2}', "&GreaterGreater;"), ('\u{2AA4}', "&glj;"),
+('\u{2AA5}', "&gla;"), ('\u{2AA6}', "&ltcc;"), ('\u{2AA7}', "&gtcc;"), ('\u{2AA8}', "&lescc;"),
+('\u{2AA9}', "&gescc;"), ('\u{2AAA}', "&smt;"), ('\u{2AAB}', "&lat;"), ('\u{2AAC}', "&smte;"),
+('\u{2AAD}', "&late;"), ('\u{2AAE}', "&bumpE;"), ('\u{2AAF}', "&pre;"), ('\u{2AB0}', "&sce;"),
+('\u{2AB3}', "&prE;"), ('\u{2AB4}', "&scE;"), ('\u{2AB5}', "&prnE;"), ('\u{2AB6}', "&scnE;"),
+('\u{2AB7}', "&prap;"), ('\u{2AB8}', "&scap;"), ('\u{2AB9}', "&prnap;"), ('\u{2ABA}', "&scnap;"),
+('\u{2ABB}', "&Pr;"), ('\u{2ABC}', "&Sc;"), ('\u{2ABD}', "&subdot;"), ('\u{2ABE}', "&supdot;"),
+('\u{2ABF}', "&subplus;"), ('\u{2AC0}', "&supplus;"), ('\u{2AC1}', "&submult;"), ('\u{2AC2}', "&supmult;"),
+('\u{2AC3}', "&subedot;"), ('\u{2AC4}', "&supedot;"), ('\u{2AC5}', "&subE;"), ('\u{2AC6}', "&supE;"),
+('\u{2AC7}', "&subsim;"), ('\u{2AC8}', "&supsim;"), ('\u{2ACB}', "&subnE;"), ('\u{2ACC}', "&supnE;"),
+('\u{2ACF}', "&csub;"), ('\u{2AD0}', "&csup;"), ('\u{2AD1}', "&csube;"), ('\u{2AD2}', "&csupe;"),
+('\u{2AD3}', "&subsup;"), ('\u{2AD4}', "&supsub;"), ('\u{2AD5}', "&subsub;"), ('\u{2AD6}', "&supsup;"),
+('\u{2AD7}', "&suphsub;"), ('\u{2AD8}', "&supdsub;"), ('\u{2AD9}', "&forkv;"), ('\u{2ADA}', "&topfork;"),
+('\u{2ADB}', "&mlcp;"), ('\u{2AE4}', "&Dashv;"), ('\u{2AE6}', "&Vdashl;"), ('\u{2AE7}', "&Barv;"),
+('\u{2AE8}', "&vBar;"), ('\u{2AE9}', "&vBarv;"), ('\u{2AEB}', "&Vbar;"), ('\u{2AEC}', "&Not;"),
+('\u{2AED}', "&bNot;"), ('\u{2AEE}', "&rnmid;"), ('\u{2AEF}', "&cirmid;"), ('\u{2AF0}', "&midcir;"),
+('\u{2AF1}', "&topcir;"), ('\u{2AF2}', "&nhpar;"), ('\u{2AF3}', "&parsim;"), ('\u{2AFD}', "&parsl;"),
+('\u{FB00}', "&fflig;"), ('\u{FB01}', "&filig;"), ('\u{FB02}', "&fllig;"), ('\u{FB03}', "&ffilig;"),
+('\u{FB04}', "&ffllig;"), ('\u{1D49C}', "&Ascr;"), ('\u{1D49E}', "&Cscr;"), ('\u{1D49F}', "&Dscr;"),
+('\u{1D4A2}', "&Gscr;"), ('\u{1D4A5}', "&Jscr;"), ('\u{1D4A6}', "&Kscr;"), ('\u{1D4A9}', "&Nscr;"),
+('\u{1D4AA}', "&Oscr;"), ('\u{1D4AB}', "&Pscr;"), ('\u{1D4AC}', "&Qscr;"), ('\u{1D4AE}', "&Sscr;"),
+('\u{1D4AF}', "&Tscr;"), ('\u{1D4B0}', "&Uscr;"), ('\u{1D4B1}', "&Vscr;"), ('\u{1D4B2}', "&Wscr;"),
+('\u{1D4B3}', "&Xscr;"), ('\u{1D4B4}', "&Yscr;"), ('\u{1D4B5}', "&Zscr;"), ('\u{1D4B6}', "&ascr;"),
+('\u{1D4B7}', "&bscr;"), ('\u{1D4B8}', "&cscr;"), ('\u{1D4B9}', "&dscr;"), ('\u{1D4BB}', "&fscr;"),
+('\u{1D4BD}', "&hscr;"), ('\u{1D4BE}', "&iscr;"), ('\u{1D4BF}', "&jscr;"), ('\u{1D4C0}', "&kscr;"),
+('\u{1D4C1}', "&lscr;"), ('\u{1D4C2}', "&mscr;"), ('\u{1D4C3}', "&nscr;"), ('\u{1D4C5}', "&pscr;"),
+('\u{1D4C6}', "&qscr;"), ('\u{1D4C7}', "&rscr;"), ('\u{1D4C8}', "&sscr;"), ('\u{1D4C9}', "&tscr;"),
+('\u{1D4CA}', "&uscr;"), ('\u{1D4CB}', "&vscr;"), ('\u{1D4CC}', "&wscr;"), ('\u{1D4CD}', "&xscr;"),
+('\u{1D4CE}', "&yscr;"), ('\u{1D4CF}', "&zscr;"), ('\u{1D504}', "&Afr;"), ('\u{1D505}', "&Bfr;"),
+('\u{1D507}', "&Dfr;"), ('\u{1D508}', "&Efr;"), ('\u{1D509}', "&Ffr;"), ('\u{1D50A}', "&Gfr;"),
+('\u{1D50D}', "&Jfr;"), ('\u{1D50E}', "&Kfr;"), ('\u{1D50F}', "&Lfr;"), ('\u{1D510}', "&Mfr;"),
+('\u{1D511}', "&Nfr;"), ('\u{1D512}', "&Ofr;"), ('\u{1D513}', "&Pfr;"), ('\u{1D514}', "&Qfr;"),
+('\u{1D516}', "&Sfr;"), ('\u{1D517}', "&Tfr;"), ('\u{1D518}', "&Ufr;"), ('\u{1D519}', "&Vfr;"),
+('\u{1D51A}', "&Wfr;"), ('\u{1D51B}', "&Xfr;"), ('\u{1D51C}', "&Yfr;"), ('\u{1D51E}', "&afr;"),
+('\u{1D51F}', "&bfr;"), ('\u{1D520}', "&cfr;"), ('\u{1D521}', "&dfr;"), ('\u{1D522}', "&efr;"),
+('\u{1D523}', "&ffr;"), ('\u{1D524}', "&gfr;"), ('\u{1D525}', "&hfr;"), ('\u{1D526}', "&ifr;"),
+('\u{1D527}', "&jfr;"), ('\u{1D528}', "&kfr;"), ('\u{1D529}', "&lfr;"), ('\u{1D52A}', "&mfr;"),
+('\u{1D52B}', "&nfr;"), ('\u{1D52C}', "&ofr;"), ('\u{1D52D}', "&pfr;"), ('\u{1D52E}', "&qfr;"),
+('\u{1D52F}', "&rfr;"), ('\u{1D530}', "&sfr;"), ('\u{1D531}', "&tfr;"), ('\u{1D532}', "&ufr;"),
+('\u{1D533}', "&vfr;"), ('\u{1D534}', "&wfr;"), ('\u{1D535}', "&xfr;"), ('\u{1D536}', "&yfr;"),
+('\u{1D537}', "&zfr;"), ('\u{1D538}', "&Aopf;"), ('\u{1D539}', "&Bopf;"), ('\u{1D53B}', "&Dopf;"),
+('\u{1D53C}', "&Eopf;"), ('\u{1D53D}', "&Fopf;"), ('\u{1D53E}', "&Gopf;"), ('\u{1D540}', "&Iopf;"),
+('\u{1D541}', "&Jopf;"), ('\u{1D542}', "&Kopf;"), ('\u{1D543}', "&Lopf;"), ('\u{1D544}', "&Mopf;"),
+('\u{1D546}', "&Oopf;"), ('\u{1D54A}', "&Sopf;"), ('\u{1D54B}', "&Topf;"), ('\u{1D54C}', "&Uopf;"),
+('\u{1D54D}', "&Vopf;"), ('\u{1D54E}', "&Wopf;"), ('\u{1D54F}', "&Xopf;"), ('\u{1D550}', "&Yopf;"),
+('\u{1D552}', "&aopf;"), ('\u{1D553}', "&bopf;"), ('\u{1D554}', "&copf;"), ('\u{1D555}', "&dopf;"),
+('\u{1D556}', "&eopf;"), ('\u{1D557}', "&fopf;"), ('\u{1D558}', "&gopf;"), ('\u{1D559}', "&hopf;"),
+('\u{1D55A}', "&iopf;"), ('\u{1D55B}', "&jopf;"), ('\u{1D55C}', "&kopf;"), ('\u{1D55D}', "&lopf;"),
+('\u{1D55E}', "&mopf;"), ('\u{1D55F}', "&nopf;"), ('\u{1D560}', "&oopf;"), ('\u{1D561}', "&popf;"),
+('\u{1D562}', "&qopf;"), ('\u{1D563}', "&ropf;"), ('\u{1D564}', "&sopf;"), ('\u{1D565}', "&topf;"),
+('\u{1D566}', "&uopf;"), ('\u{1D567}', "&vopf;"), ('\u{1D568}', "&wopf;"), ('\u{1D569}', "&xopf;"),
+('\u{1D56A}', "&yopf;"), ('\u{1D56B}', "&zopf;")];
@@ -0,0 +1,182 @@
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+
+#[cfg(feature = "alloc")]
+use error::IllegalXmlChar;
+use escape::{EscapeTable, table};
+
+
+static XML: EscapeTable<'static> = table(&[
+    // The predefined entities
+    (b'&', "&amp;"), (b'<', "&lt;"), (b'>', "&gt;"), (b'"', "&quot;"), (b'\'', "&apos;"),
+    // Control characters other than tab, line feed and carriage return are not allowed in XML 1.0
+    // at all, not even as character references.
+    (0x00, "\u{FFFD}"), (0x01, "\u{FFFD}"), (0x02, "\u{FFFD}"), (0x03, "\u{FFFD}"),
+    (0x04, "\u{FFFD}"), (0x05, "\u{FFFD}"), (0x06, "\u{FFFD}"), (0x07, "\u{FFFD}"),
+    (0x08, "\u{FFFD}"), (0x0B, "\u{FFFD}"), (0x0C, "\u{FFFD}"), (0x0E, "\u{FFFD}"),
+    (0x0F, "\u{FFFD}"), (0x10, "\u{FFFD}"), (0x11, "\u{FFFD}"), (0x12, "\u{FFFD}"),
+    (0x13, "\u{FFFD}"), (0x14, "\u{FFFD}"), (0x15, "\u{FFFD}"), (0x16, "\u{FFFD}"),
+    (0x17, "\u{FFFD}"), (0x18, "\u{FFFD}"), (0x19, "\u{FFFD}"), (0x1A, "\u{FFFD}"),
+    (0x1B, "\u{FFFD}"), (0x1C, "\u{FFFD}"), (0x1D, "\u{FFFD}"), (0x1E, "\u{FFFD}"),
+    (0x1F, "\u{FFFD}"),
+]);
+
+/// Escape a byte stream for inclusion into XML 1.0 or XHTML documents.
+///
+/// Only the five predefined entities (`&amp;`, `&lt;`, `&gt;`, `&quot;` and `&apos;`) are used in
+/// the output. Characters which are not allowed in XML 1.0 documents, that is the control
+/// characters other than tab, line feed and carriage return, and U+FFFE and U+FFFF, are replaced
+/// with U+FFFD REPLACEMENT CHARACTER. Use `escape_xml_bytes` to reject them instead.
+///
+/// The implementation works with bytes interpreting them to be UTF-8.
+///
+/// # Usage
+///
+/// ```
+/// use marksman_escape::EscapeXml;
+/// let string = "<a title='\u{1}'>&</a>";
+/// let escaped = String::from_utf8(EscapeXml::new(string.bytes()).collect()).unwrap();
+/// assert_eq!("&lt;a title=&apos;\u{FFFD}&apos;&gt;&amp;&lt;/a&gt;", &*escaped);
+/// ```
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct EscapeXml<I: Iterator<Item=u8>> {
+    inner: I,
+    /// Rest of the replacement being emitted.
+    pending: &'static [u8],
+    /// How many bytes of the `EF BF` prefix shared by U+FFFE and U+FFFF were just passed through.
+    prefix: u8,
+}
+
+impl<I: Iterator<Item=u8>> EscapeXml<I> {
+    /// Create an iterator adaptor which will escape all the bytes of internal iterator.
+    pub fn new(i: I) -> EscapeXml<I> {
+        EscapeXml {
+            inner: i,
+            pending: &[],
+            prefix: 0,
+        }
+    }
+}
+
+impl<I: Iterator<Item=u8>> Iterator for EscapeXml<I> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if let Some((&first, rest)) = self.pending.split_first() {
+            self.pending = rest;
+            return Some(first);
+        }
+        let ch = self.inner.next()?;
+        if let Some((&first, rest)) = XML.get(ch).as_bytes().split_first() {
+            self.prefix = 0;
+            self.pending = rest;
+            return Some(first);
+        }
+        match (self.prefix, ch) {
+            (_, 0xEF) => self.prefix = 1,
+            (1, 0xBF) => self.prefix = 2,
+            (2, 0xBE) | (2, 0xBF) => {
+                // The last byte of U+FFFE or U+FFFF, replace with the last byte of U+FFFD.
+                self.prefix = 0;
+                return Some(0xBD);
+            },
+            _ => self.prefix = 0
+        }
+        Some(ch)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (l, u) = self.inner.size_hint();
+        let buffered = self.pending.len();
+        let upper = u.and_then(|u_| u_.checked_mul("&quot;".len()))
+                     .and_then(|u_| u_.checked_add(buffered));
+        (l.saturating_add(buffered), upper)
+    }
+}
+
+/// Find the first character not allowed in XML 1.0 documents.
+#[cfg(feature = "alloc")]
+fn find_illegal(bytes: &[u8]) -> Option<IllegalXmlChar> {
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'\t' | b'\n' | b'\r' => {},
+            0x00..=0x1F => return Some(IllegalXmlChar::new(i, b as char)),
+            0xEF => match bytes.get(i + 1..i + 3) {
+                Some(&[0xBF, 0xBE]) => return Some(IllegalXmlChar::new(i, '\u{FFFE}')),
+                Some(&[0xBF, 0xBF]) => return Some(IllegalXmlChar::new(i, '\u{FFFF}')),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Escape a byte slice for inclusion into XML 1.0 or XHTML documents.
+///
+/// Same as `EscapeXml`, except that characters not allowed in XML 1.0 are reported rather than
+/// replaced. The slice is returned as is if it contains nothing to escape.
+///
+/// # Usage
+///
+/// ```
+/// use marksman_escape::escape_xml_bytes;
+/// assert_eq!(&*escape_xml_bytes(b"<'&'>").unwrap(), b"&lt;&apos;&amp;&apos;&gt;");
+/// let error = escape_xml_bytes(b"a\x07b").unwrap_err();
+/// assert_eq!((error.offset(), error.char()), (1, '\u{7}'));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn escape_xml_bytes<'a>(bytes: &'a [u8]) -> Result<Cow<'a, [u8]>, IllegalXmlChar> {
+    match find_illegal(bytes) {
+        Some(error) => Err(error),
+        None => Ok(XML.escape_bytes(bytes))
+    }
+}
+
+/// Escape a string slice for inclusion into XML 1.0 or XHTML documents.
+///
+/// Same as `EscapeXml`, except that characters not allowed in XML 1.0 are reported rather than
+/// replaced. The slice is returned as is if it contains nothing to escape.
+#[cfg(feature = "alloc")]
+pub fn escape_xml_str<'a>(string: &'a str) -> Result<Cow<'a, str>, IllegalXmlChar> {
+    match find_illegal(string.as_bytes()) {
+        Some(error) => Err(error),
+        None => Ok(XML.escape_str(string))
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::prelude::v1::*;
+    use ::{EscapeXml, escape_xml_bytes, escape_xml_str};
+
+    fn run_test(from: &str, to: &str) {
+        let ev = EscapeXml::new(from.bytes()).collect::<Vec<_>>();
+        assert_eq!(String::from_utf8(ev).unwrap(), to);
+    }
+
+    #[test]
+    fn escape_xml(){
+        run_test("Hello world!", "Hello world!");
+        run_test("<a href=\"?a=1&b=2\">'`{}`'</a>",
+                 "&lt;a href=&quot;?a=1&amp;b=2&quot;&gt;&apos;`{}`&apos;&lt;/a&gt;");
+        run_test("\t\r\n\u{0}\u{8}\u{B}\u{C}\u{1F}\u{7F}",
+                 "\t\r\n\u{FFFD}\u{FFFD}\u{FFFD}\u{FFFD}\u{FFFD}\u{7F}");
+        run_test("\u{FFFE}\u{FFFF}\u{FFFD}\u{FFFC}\u{EFFFF}\u{FFFE}",
+                 "\u{FFFD}\u{FFFD}\u{FFFD}\u{FFFC}\u{EFFFF}\u{FFFD}");
+        run_test("\u{FFFF}\u{1}\u{FFFF}", "\u{FFFD}\u{FFFD}\u{FFFD}");
+    }
+
+    #[test]
+    fn escape_xml_slices(){
+        assert_eq!(escape_xml_str("a < b").unwrap(), "a &lt; b");
+        assert_eq!(&*escape_xml_bytes(b"plain").unwrap(), b"plain");
+        let error = escape_xml_str("ab\u{FFFE}").unwrap_err();
+        assert_eq!((error.offset(), error.char()), (2, '\u{FFFE}'));
+        let error = escape_xml_str("\u{FFFD}\u{FFFF}").unwrap_err();
+        assert_eq!((error.offset(), error.char()), (3, '\u{FFFF}'));
+        assert_eq!(escape_xml_str("\u{0}").unwrap_err().to_string(),
+                   "character U+0000 is not allowed in XML at byte 0");
+    }
+}
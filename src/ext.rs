@@ -0,0 +1,105 @@
+use alloc::borrow::Cow;
+
+use escape::{Escape, escape_bytes, escape_str};
+use unescape::{Unescape, unescape_bytes, unescape_str};
+
+
+/// Marks the implementation of `HtmlEscapeExt` for iterators.
+pub enum ByIterator {}
+
+/// Marks the implementations of `HtmlEscapeExt` for string and byte slices.
+pub enum BySlice {}
+
+/// Shorthands for escaping and unescaping iterators and slices.
+///
+/// For byte iterators these return the `Escape::new` and `Unescape::new` adaptors, and for string
+/// and byte slices the results of `escape_str`, `unescape_str`, `escape_bytes` and
+/// `unescape_bytes`, which borrow the slice if there is nothing to replace.
+///
+/// The type parameter only tells the implementations for iterators and slices apart, and is always
+/// inferred.
+///
+/// # Usage
+///
+/// ```
+/// use marksman_escape::prelude::*;
+/// assert_eq!("<hello>".escape_html(), "&lt;hello&gt;");
+/// assert_eq!(&*b"&lt;hello&gt;"[..].unescape_html(), b"<hello>");
+/// let escaped = "<hello>".bytes().escape_html().collect::<Vec<_>>();
+/// assert_eq!(escaped, b"&lt;hello&gt;");
+/// ```
+pub trait HtmlEscapeExt<Kind> {
+    /// The escaped output.
+    type Escaped;
+    /// The unescaped output.
+    type Unescaped;
+
+    /// Escape all the characters which `Escape::new` escapes.
+    fn escape_html(self) -> Self::Escaped;
+
+    /// Unescape all the character references, like `Unescape::new` does.
+    fn unescape_html(self) -> Self::Unescaped;
+}
+
+impl<I: Iterator<Item=u8>> HtmlEscapeExt<ByIterator> for I {
+    type Escaped = Escape<'static, I>;
+    type Unescaped = Unescape<I>;
+
+    fn escape_html(self) -> Escape<'static, I> {
+        Escape::new(self)
+    }
+
+    fn unescape_html(self) -> Unescape<I> {
+        Unescape::new(self)
+    }
+}
+
+impl<'a> HtmlEscapeExt<BySlice> for &'a str {
+    type Escaped = Cow<'a, str>;
+    type Unescaped = Cow<'a, str>;
+
+    fn escape_html(self) -> Cow<'a, str> {
+        escape_str(self)
+    }
+
+    fn unescape_html(self) -> Cow<'a, str> {
+        unescape_str(self)
+    }
+}
+
+impl<'a> HtmlEscapeExt<BySlice> for &'a [u8] {
+    type Escaped = Cow<'a, [u8]>;
+    type Unescaped = Cow<'a, [u8]>;
+
+    fn escape_html(self) -> Cow<'a, [u8]> {
+        escape_bytes(self)
+    }
+
+    fn unescape_html(self) -> Cow<'a, [u8]> {
+        unescape_bytes(self)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+    use std::prelude::v1::*;
+    use ::prelude::*;
+
+    #[test]
+    fn ext(){
+        assert_eq!("<a>&amp;".escape_html(), "&lt;a&gt;&amp;amp;");
+        assert_eq!("<a>&amp;".unescape_html(), "<a>&");
+        assert_eq!("hello".escape_html(), Cow::Borrowed("hello"));
+        assert_eq!(String::from("a&lt;b").unescape_html(), "a<b");
+
+        let bytes: &[u8] = b"<a>&amp;";
+        assert_eq!(&*bytes.escape_html(), b"&lt;a&gt;&amp;amp;");
+        assert_eq!(&*bytes.unescape_html(), b"<a>&");
+        assert_eq!(&*Vec::from(bytes).unescape_html(), b"<a>&");
+
+        assert_eq!(bytes.iter().cloned().escape_html().collect::<Vec<_>>(), b"&lt;a&gt;&amp;amp;");
+        assert_eq!(bytes.iter().cloned().unescape_html().collect::<Vec<_>>(), b"<a>&");
+    }
+}
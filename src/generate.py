@@ -44,18 +44,12 @@ if __name__ == "__main__":
     with open("unescape_named_gen.rs", "w") as f:
         f.write("// This file is autogenerated by generate.py, do not modify\n\n")
 
-        # LONGEST_NAMED_REFERENCE generation
-        longest = max(map(len, html.entities.html5.keys()))
-        f.write("/// Length of the longest supported/known character reference name.\n")
-        f.write("pub const LONGEST_NAMED_REFERENCE : usize = {};\n\n".format(longest))
-
-
         # DECODED and TRIE generation
         trie = TrieBuilder()
         entities = html.entities.html5
         indexes = {}
         num = 0
-        f.write("pub static DECODED: [&'static [u8]; {}] = [\n".format(len(entities)))
+        f.write("pub static DECODED: [&[u8]; {}] = [\n".format(len(entities)))
         letters = []
         for k, v in sorted(entities.items(), key=lambda x: x[0]):
             indexes[v] = num
@@ -65,3 +59,26 @@ if __name__ == "__main__":
         f.write(",\n".join([', '.join(letters[i:i+6]) for i in range(0, len(letters), 6)]))
         f.write('];\n\n')
         f.write("pub static TRIE: Trie = {};".format(trie.build()))
+
+    with open("escape_named_gen.rs", "w") as f:
+        f.write("// This file is autogenerated by generate.py, do not modify\n\n")
+
+        # ENCODED generation, the shortest name of every single character. Among names of the same
+        # length, the legacy ones (which HTML4 had) are preferred, then the lowercase ones.
+        shortest = {}
+        for k, v in html.entities.html5.items():
+            if len(v) != 1 or not k.endswith(";"):
+                continue
+            key = (len(k), k[:-1] not in html.entities.html5, k.swapcase())
+            if v not in shortest or key < shortest[v][0]:
+                shortest[v] = (key, k)
+        longest = max(len(k) for _, k in shortest.values()) + 1
+        f.write("/// Length of the longest named reference in `ENCODED`, including the `&` and `;`.\n")
+        f.write("pub const LONGEST_ENCODED_REFERENCE : usize = {};\n\n".format(longest))
+        f.write("/// The shortest named reference of every character which has one, sorted by the "
+                "character.\n")
+        f.write("pub static ENCODED: [(char, &str); {}] = [\n".format(len(shortest)))
+        pairs = ["('\\u{{{:X}}}', \"&{}\")".format(ord(v), k)
+                 for v, (_, k) in sorted(shortest.items())]
+        f.write(",\n".join([', '.join(pairs[i:i+4]) for i in range(0, len(pairs), 4)]))
+        f.write('];\n')
@@ -33,11 +33,79 @@
 //! Note, that both escape and unescape benchmarks test how fast the input is consumed, rather than
 //! produced. They are likely to improve further as codegen for `Iterator`s is improved and my own
 //! battles against LLVM are concluded.
+//!
+//! ### `no_std`
+//!
+//! The crate is `#![no_std]`. Without its default `std` feature `EscapeReader`, `EscapeWriter`,
+//! `UnescapeReader` and `UnescapeWriter` are not available, and the SIMD search in the slice APIs
+//! is only used if the target enables AVX2 or SSE2, as the CPU cannot be queried at runtime.
+//!
+//! The `alloc` feature, which `std` enables, provides the APIs which allocate: the slice APIs
+//! returning a `Cow`, such as `escape_str`, all the unescaping and `HtmlEscapeExt`. Without it the
+//! escaping iterators, `Escaper` for writing into fixed-size buffers, `escaped_len` and the
+//! `Escaped` and `EscapedBytes` wrappers for `Display` are still available.
 
-pub use escape::{Escape};
-pub use unescape::{Unescape};
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(any(feature = "std", test))]
+#[cfg_attr(test, macro_use)]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+pub use entities::{Entities};
+pub use error::{IllegalXmlChar, UnescapeError, UnescapeErrorKind};
+pub use escape::{Escape, EscapeChars, EscapeContext, EscapeTable, EscapeTableBuilder, Escaped,
+                 EscapedBytes, Escaper, escaped_len};
+#[cfg(feature = "alloc")]
+pub use escape::{escape_bytes, escape_str};
+pub use escape_ascii::{EscapeAscii};
+#[cfg(feature = "alloc")]
+pub use escape_ascii::{escape_ascii_bytes, escape_ascii_str};
+pub use escape_named::{EscapeNamed};
+#[cfg(feature = "alloc")]
+pub use unescape::{StreamingUnescaper, Unescape, UnescapeChars, strict_unescape, unescape_bytes,
+                   unescape_str, unescaped_len_upper_bound};
+#[cfg(feature = "alloc")]
+pub use ext::{ByIterator, BySlice, HtmlEscapeExt};
+pub use escape_xml::{EscapeXml};
+#[cfg(feature = "alloc")]
+pub use escape_xml::{escape_xml_bytes, escape_xml_str};
+#[cfg(feature = "std")]
+pub use reader::{EscapeReader, UnescapeReader};
 pub use unescape_named::{get_named_ref};
+#[cfg(feature = "std")]
+pub use writer::{EscapeWriter, UnescapeWriter};
 
+#[cfg(feature = "alloc")]
+mod entities;
+mod error;
 mod escape;
+mod escape_ascii;
+mod escape_named;
+mod escape_xml;
+#[cfg(feature = "alloc")]
+mod ext;
+#[cfg(feature = "std")]
+mod reader;
+mod scan;
+#[cfg(test)]
+mod test_util;
+#[cfg(feature = "alloc")]
 mod unescape;
 mod unescape_named;
+mod utf8;
+#[cfg(feature = "std")]
+mod writer;
+
+/// Re-exports of the extension traits, for glob imports.
+///
+/// ```
+/// use marksman_escape::prelude::*;
+/// assert_eq!("a &amp; b".unescape_html(), "a & b");
+/// ```
+#[cfg(feature = "alloc")]
+pub mod prelude {
+    pub use ext::HtmlEscapeExt;
+}
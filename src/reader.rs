@@ -0,0 +1,239 @@
+use alloc::vec::Vec;
+use core::cmp;
+use std::io::{self, Read};
+
+use escape::{EscapeContext, EscapeTable};
+use unescape::Decoder;
+use writer::CHUNK_SIZE;
+
+
+/// A reader which escapes all the data read from the inner reader.
+///
+/// The same tables as in `Escape` are used, so `EscapeReader::new(r)` produces the same output as
+/// `Escape::new` would. The inner reader is read in chunks of up to several kilobytes and errors
+/// it returns are passed on to the caller.
+///
+/// # Usage
+///
+/// ```
+/// use std::io::Read;
+/// use marksman_escape::EscapeReader;
+/// let mut escaped = String::new();
+/// EscapeReader::new(&b"<hello>&world</hello>"[..]).read_to_string(&mut escaped).unwrap();
+/// assert_eq!("&lt;hello&gt;&amp;world&lt;/hello&gt;", escaped);
+/// ```
+pub struct EscapeReader<'t, R: Read> {
+    inner: R,
+    table: &'t EscapeTable<'t>,
+    index: usize, // Index into the buffer
+    buffer: Vec<u8>,
+}
+
+impl<'t, R: Read> EscapeReader<'t, R> {
+    /// Create a reader which escapes the same characters as `Escape::new` does.
+    pub fn new(inner: R) -> EscapeReader<'t, R> {
+        EscapeReader::with_table(inner, EscapeTable::conservative())
+    }
+
+    /// Create a reader which escapes only the characters significant in the given context.
+    pub fn with_context(inner: R, context: EscapeContext) -> EscapeReader<'t, R> {
+        EscapeReader::with_table(inner, context.table())
+    }
+
+    /// Create a reader which escapes characters according to the provided table.
+    pub fn with_table(inner: R, table: &'t EscapeTable<'t>) -> EscapeReader<'t, R> {
+        EscapeReader {
+            inner,
+            table,
+            index: 0,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Return the inner reader, dropping any escaped data which has not been read yet.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<'t, R: Read> Read for EscapeReader<'t, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if self.index == self.buffer.len() {
+            let mut input = [0; CHUNK_SIZE];
+            let len = cmp::min(buf.len(), CHUNK_SIZE);
+            let read = self.inner.read(&mut input[..len])?;
+            self.buffer.clear();
+            self.index = 0;
+            self.table.escape_to(&input[..read], &mut self.buffer);
+        }
+        Ok(copy_buffered(&self.buffer, &mut self.index, buf))
+    }
+}
+
+/// A reader which unescapes all the data read from the inner reader.
+///
+/// The output is the same as `Unescape` would produce for all of the data in the inner reader.
+/// The inner reader is read in chunks of up to several kilobytes and errors it returns are passed
+/// on to the caller.
+///
+/// # Usage
+///
+/// ```
+/// use std::io::Read;
+/// use marksman_escape::UnescapeReader;
+/// let mut unescaped = String::new();
+/// UnescapeReader::new(&b"&lt;hello&gt;&amp;world&#60;/hello&#x3e;"[..])
+///     .read_to_string(&mut unescaped).unwrap();
+/// assert_eq!("<hello>&world</hello>", unescaped);
+/// ```
+pub struct UnescapeReader<R: Read> {
+    inner: R,
+    decoder: Decoder,
+    index: usize, // Index into the buffer
+    buffer: Vec<u8>,
+}
+
+impl<R: Read> UnescapeReader<R> {
+    /// Create a reader which unescapes the same character references as `Unescape` does.
+    pub fn new(inner: R) -> UnescapeReader<R> {
+        UnescapeReader {
+            inner,
+            decoder: Decoder::new(),
+            index: 0,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Return the inner reader, dropping any unescaped data which has not been read yet.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for UnescapeReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        // A chunk of input might be swallowed whole by a partial character reference, so keep
+        // reading until there is some output or the inner reader is exhausted.
+        while self.index == self.buffer.len() {
+            let mut input = [0; CHUNK_SIZE];
+            let len = cmp::min(buf.len(), CHUNK_SIZE);
+            let read = self.inner.read(&mut input[..len])?;
+            self.buffer.clear();
+            self.index = 0;
+            if read == 0 {
+                self.decoder.finish(&mut self.buffer);
+                break;
+            }
+            self.decoder.feed(&input[..read], &mut self.buffer);
+        }
+        Ok(copy_buffered(&self.buffer, &mut self.index, buf))
+    }
+}
+
+/// Copy as much of the buffer starting at `index` as fits into `buf`.
+fn copy_buffered(buffer: &[u8], index: &mut usize, buf: &mut [u8]) -> usize {
+    let len = cmp::min(buf.len(), buffer.len() - *index);
+    buf[..len].copy_from_slice(&buffer[*index..*index + len]);
+    *index += len;
+    len
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::io::{self, Read};
+    use std::prelude::v1::*;
+    use ::{Escape, EscapeReader, Unescape, UnescapeReader};
+    use unescape::LONGEST_BUFFERED_NUMERIC_REFERENCE;
+
+    /// A reader returning at most 2 bytes at a time, interrupted every other call, and failing
+    /// at the end of data.
+    struct Trickle<'a>(&'a [u8], bool);
+
+    impl<'a> Read for Trickle<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.1 = !self.1;
+            if self.1 {
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "interrupted"));
+            } else if self.0.is_empty() {
+                return Err(io::Error::other("broken"));
+            }
+            let len = ::std::cmp::min(::std::cmp::min(buf.len(), self.0.len()), 2);
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn escape_reader(){
+        let input = "<hello>&{world}</hello>".repeat(1000);
+        let mut output = Vec::new();
+        EscapeReader::new(input.as_bytes()).read_to_end(&mut output).unwrap();
+        assert_eq!(output, Escape::new(input.bytes()).collect::<Vec<_>>());
+
+        let mut output = Vec::new();
+        let err = EscapeReader::new(Trickle(b"<&>", false)).read_to_end(&mut output).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+        assert_eq!(output, b"&lt;&amp;&gt;");
+    }
+
+    #[test]
+    fn unescape_reader(){
+        let input = "&lt;hello&gt;&amp;world&#60;/hello&#x3e;&AMP&notin;&amp".repeat(1000);
+        let mut output = Vec::new();
+        UnescapeReader::new(input.as_bytes()).read_to_end(&mut output).unwrap();
+        assert_eq!(output, Unescape::new(input.bytes()).collect::<Vec<_>>());
+
+        let mut output = Vec::new();
+        let err = UnescapeReader::new(Trickle(b"&lt;&#x3e;", false)).read_to_end(&mut output)
+                                                                    .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+        assert_eq!(output, b"<>");
+    }
+
+    #[test]
+    fn unescape_reader_long_reference(){
+        let mut input = b"&#".to_vec();
+        input.resize(4 << 20, b'0');
+        input.push(b'$');
+        let mut reader = UnescapeReader::new(&*input);
+        let mut output = Vec::new();
+        let mut buf = [0; 4096];
+        loop {
+            let read = reader.read(&mut buf).unwrap();
+            assert!(reader.buffer.capacity() <= 2 * LONGEST_BUFFERED_NUMERIC_REFERENCE);
+            if read == 0 {
+                break;
+            }
+            output.extend_from_slice(&buf[..read]);
+        }
+        assert_eq!(output, "\u{FFFD}$".as_bytes());
+    }
+}
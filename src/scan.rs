@@ -0,0 +1,392 @@
+#[cfg(feature = "alloc")]
+use core::convert::Infallible;
+use core::convert::TryInto;
+
+/// Most bytes a set can hold to be searched by comparing against each of them in turn.
+const MAX_NEEDLES : usize = 24;
+
+/// Most ranges of bytes the word-at-a-time search checks for, merging the closest ones of a set
+/// which has more.
+const SWAR_RANGES : usize = 4;
+
+const ONES : u64 = 0x0101_0101_0101_0101;
+const LOW_BITS : u64 = 0x7F * ONES;
+
+
+/// A set of ASCII bytes, such as the ones an `EscapeTable` escapes, which a byte slice can be
+/// searched for several bytes at a time.
+///
+/// Depending on the CPU and the size of the set, the slice is searched 32 bytes at a time with
+/// AVX2, 16 bytes at a time with SSE2 or else a word at a time.
+#[derive(Clone)]
+pub struct ByteSet {
+    /// Bit `b >> 4` of `nibbles[b & 0xF]` is set for every byte `b` in the set.
+    nibbles: [u8; 16],
+    /// The bytes in the set, as long as there are at most `MAX_NEEDLES` of them.
+    #[cfg_attr(not(any(target_arch = "x86", target_arch = "x86_64")), allow(dead_code))]
+    needles: [u8; MAX_NEEDLES],
+    len: usize,
+    /// Ranges of bytes covering the set, as the amounts to add to the low bits of every byte of a
+    /// word for its high bit to tell whether the byte is at least the first of the range, and
+    /// whether it is past the last one. The unused ones are zero and never match.
+    ranges: [(u64, u64); SWAR_RANGES],
+}
+
+impl ByteSet {
+    /// The set of the ASCII bytes marked in the table.
+    pub const fn new(table: &[bool; 128]) -> ByteSet {
+        let mut set = ByteSet {
+            nibbles: [0; 16],
+            needles: [0; MAX_NEEDLES],
+            len: 0,
+            ranges: [(0, 0); SWAR_RANGES],
+        };
+        let (mut first, mut last, mut runs) = ([0; 64], [0; 64], 0);
+        let mut byte = 0;
+        while byte < table.len() {
+            if table[byte] {
+                set.nibbles[byte & 0xF] |= 1 << (byte >> 4);
+                if set.len < MAX_NEEDLES {
+                    set.needles[set.len] = byte as u8;
+                }
+                set.len += 1;
+                if runs > 0 && last[runs - 1] + 1 == byte {
+                    last[runs - 1] = byte;
+                } else {
+                    first[runs] = byte;
+                    last[runs] = byte;
+                    runs += 1;
+                }
+            }
+            byte += 1;
+        }
+        // Merge the two runs closest to each other until there are few enough of them.
+        while runs > SWAR_RANGES {
+            let mut closest = 0;
+            let mut i = 1;
+            while i + 1 < runs {
+                if first[i + 1] - last[i] < first[closest + 1] - last[closest] {
+                    closest = i;
+                }
+                i += 1;
+            }
+            last[closest] = last[closest + 1];
+            i = closest + 1;
+            while i + 1 < runs {
+                first[i] = first[i + 1];
+                last[i] = last[i + 1];
+                i += 1;
+            }
+            runs -= 1;
+        }
+        let mut i = 0;
+        while i < runs {
+            set.ranges[i] = (ONES * (0x80 - first[i] as u64), ONES * (0x7F - last[i] as u64));
+            i += 1;
+        }
+        set
+    }
+
+    /// The set of the single byte, which must be ASCII.
+    #[cfg(feature = "alloc")]
+    pub const fn single(byte: u8) -> ByteSet {
+        let mut table = [false; 128];
+        table[byte as usize] = true;
+        ByteSet::new(&table)
+    }
+
+    /// Whether the byte is in the set.
+    #[inline]
+    pub fn contains(&self, byte: u8) -> bool {
+        byte < 0x80 && (self.nibbles[(byte & 0xF) as usize] >> (byte >> 4)) & 1 != 0
+    }
+
+    /// Position of the first byte in the slice which is in the set.
+    pub fn find(&self, bytes: &[u8]) -> Option<usize> {
+        self.try_for_each(bytes, Err).err()
+    }
+
+    /// Call `f` with the position of every byte in the slice which is in the set, in order.
+    #[cfg(feature = "alloc")]
+    pub fn for_each<F: FnMut(usize)>(&self, bytes: &[u8], mut f: F) {
+        let _ = self.try_for_each(bytes, |i| -> Result<(), Infallible> {
+            f(i);
+            Ok(())
+        });
+    }
+
+    /// Call `f` with the position of every byte in the slice which is in the set, in order, until
+    /// it fails.
+    ///
+    /// The search is picked once and `f` is called from within its loop, so searching for many
+    /// bytes costs no more setup than searching for the first one.
+    #[inline]
+    pub fn try_for_each<E, F>(&self, bytes: &[u8], f: F) -> Result<(), E>
+    where F: FnMut(usize) -> Result<(), E> {
+        if self.len == 0 {
+            return Ok(());
+        }
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if bytes.len() >= 32 && x86::has_avx2() {
+                // The CPU was just checked to support AVX2.
+                return unsafe { x86::each_avx2(self, bytes, f) };
+            }
+            if bytes.len() >= 16 && self.len <= MAX_NEEDLES && x86::has_sse2() {
+                // The CPU was just checked to support SSE2.
+                return unsafe { x86::each_sse2(self, bytes, f) };
+            }
+        }
+        self.each_swar(bytes, f)
+    }
+
+    /// Search a byte at a time.
+    fn each_bytewise<E, F>(&self, bytes: &[u8], mut f: F) -> Result<(), E>
+    where F: FnMut(usize) -> Result<(), E> {
+        for (i, &b) in bytes.iter().enumerate() {
+            if self.contains(b) {
+                f(i)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Search a 64-bit word at a time, checking which of its bytes fall in any of the ranges
+    /// covering the set and then which of those are in the set itself.
+    fn each_swar<E, F>(&self, bytes: &[u8], mut f: F) -> Result<(), E>
+    where F: FnMut(usize) -> Result<(), E> {
+        let mut chunks = bytes.chunks_exact(8);
+        for (i, chunk) in chunks.by_ref().enumerate() {
+            let word = u64::from_le_bytes(chunk.try_into().unwrap());
+            let low = word & LOW_BITS;
+            let mut found = 0;
+            for &(first, past) in &self.ranges {
+                // Adding to the low bits never carries across bytes, unlike subtracting from the
+                // whole word.
+                found |= (low + first) & !(low + past);
+            }
+            // Bytes with the high bit set are not ASCII.
+            found &= !word & !LOW_BITS;
+            while found != 0 {
+                let at = i * 8 + found.trailing_zeros() as usize / 8;
+                if self.contains(bytes[at]) {
+                    f(at)?;
+                }
+                found &= found - 1;
+            }
+        }
+        let searched = bytes.len() - chunks.remainder().len();
+        self.each_bytewise(chunks.remainder(), |i| f(searched + i))
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86 {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    use super::{ByteSet, MAX_NEEDLES};
+
+    /// Whether the CPU supports AVX2. Without `std` only the target features enabled at compile
+    /// time are known.
+    #[inline]
+    pub fn has_avx2() -> bool {
+        #[cfg(feature = "std")]
+        {
+            std::is_x86_feature_detected!("avx2")
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            cfg!(target_feature = "avx2")
+        }
+    }
+
+    /// Whether the CPU supports SSE2.
+    #[inline]
+    pub fn has_sse2() -> bool {
+        #[cfg(feature = "std")]
+        {
+            std::is_x86_feature_detected!("sse2")
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            cfg!(target_feature = "sse2")
+        }
+    }
+
+    /// Call `f` with the position of every set bit of the mask, from the lowest.
+    #[inline(always)]
+    fn each_bit<E, F>(mut mask: u32, mut f: F) -> Result<(), E>
+    where F: FnMut(usize) -> Result<(), E> {
+        while mask != 0 {
+            f(mask.trailing_zeros() as usize)?;
+            mask &= mask - 1;
+        }
+        Ok(())
+    }
+
+    /// Search 32 bytes at a time, looking both nibbles of every byte up in the bitmap of the set.
+    ///
+    /// The slice must be at least 32 bytes long and the CPU must support AVX2.
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn each_avx2<E, F>(set: &ByteSet, bytes: &[u8], mut f: F) -> Result<(), E>
+    where F: FnMut(usize) -> Result<(), E> {
+        let rows = _mm256_broadcastsi128_si256(_mm_loadu_si128(set.nibbles.as_ptr() as *const _));
+        // High nibbles of 8 and above are not ASCII, so they never select a bit.
+        let columns = _mm256_setr_epi8(1, 2, 4, 8, 16, 32, 64, -128, 0, 0, 0, 0, 0, 0, 0, 0,
+                                       1, 2, 4, 8, 16, 32, 64, -128, 0, 0, 0, 0, 0, 0, 0, 0);
+        let mask = |i: usize| {
+            let chunk = _mm256_loadu_si256(bytes.as_ptr().add(i) as *const _);
+            let low = _mm256_and_si256(chunk, _mm256_set1_epi8(0x0F));
+            let high = _mm256_and_si256(_mm256_srli_epi16(chunk, 4), _mm256_set1_epi8(0x0F));
+            let found = _mm256_and_si256(_mm256_shuffle_epi8(rows, low),
+                                         _mm256_shuffle_epi8(columns, high));
+            !(_mm256_movemask_epi8(_mm256_cmpeq_epi8(found, _mm256_setzero_si256())) as u32)
+        };
+        let mut i = 0;
+        while i + 32 <= bytes.len() {
+            each_bit(mask(i), |bit| f(i + bit))?;
+            i += 32;
+        }
+        if i < bytes.len() {
+            // Search the last 32 bytes, skipping the ones already searched.
+            let last = bytes.len() - 32;
+            each_bit(mask(last) >> (i - last), |bit| f(i + bit))?;
+        }
+        Ok(())
+    }
+
+    /// Search 16 bytes at a time, comparing them against every byte in the set.
+    ///
+    /// The slice must be at least 16 bytes long, the set must hold at most `MAX_NEEDLES` bytes and
+    /// the CPU must support SSE2.
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn each_sse2<E, F>(set: &ByteSet, bytes: &[u8], mut f: F) -> Result<(), E>
+    where F: FnMut(usize) -> Result<(), E> {
+        let mut needles = [_mm_setzero_si128(); MAX_NEEDLES];
+        for (splat, &needle) in needles.iter_mut().zip(&set.needles[..set.len]) {
+            *splat = _mm_set1_epi8(needle as i8);
+        }
+        let needles = &needles[..set.len];
+        let mask = |i: usize| {
+            let chunk = _mm_loadu_si128(bytes.as_ptr().add(i) as *const _);
+            let mut found = _mm_setzero_si128();
+            for &needle in needles {
+                found = _mm_or_si128(found, _mm_cmpeq_epi8(chunk, needle));
+            }
+            _mm_movemask_epi8(found) as u32
+        };
+        let mut i = 0;
+        while i + 16 <= bytes.len() {
+            each_bit(mask(i), |bit| f(i + bit))?;
+            i += 16;
+        }
+        if i < bytes.len() {
+            // Search the last 16 bytes, skipping the ones already searched.
+            let last = bytes.len() - 16;
+            each_bit(mask(last) >> (i - last), |bit| f(i + bit))?;
+        }
+        Ok(())
+    }
+}
+
+
+
+#[cfg(test)]
+mod test {
+    use std::prelude::v1::*;
+    use super::{ByteSet, MAX_NEEDLES};
+
+    static SETS: &[&[u8]] = &[
+        b"", b"&", b"&<>", b"\0\x7F", b"&<>\"'`=\t\n\x0C\r ", b"&<>\"'`!$%()+=@[]{}",
+        b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+    ];
+
+    fn byte_set(bytes: &[u8]) -> ByteSet {
+        let mut table = [false; 128];
+        for &b in bytes {
+            table[b as usize] = true;
+        }
+        ByteSet::new(&table)
+    }
+
+    /// Positions found by every search the CPU supports, including the one `for_each` picks.
+    fn searches(set: &ByteSet, bytes: &[u8]) -> Vec<Vec<usize>> {
+        type Callback<'a> = &'a mut dyn FnMut(usize) -> Result<(), ()>;
+        fn collect(search: &dyn Fn(Callback)) -> Vec<usize> {
+            let mut found = Vec::new();
+            search(&mut |i| {
+                found.push(i);
+                Ok(())
+            });
+            found
+        }
+        let mut found = vec![collect(&|f| set.try_for_each(bytes, f).unwrap()),
+                             collect(&|f| set.each_bytewise(bytes, f).unwrap())];
+        found.push(collect(&|f| set.each_swar(bytes, f).unwrap()));
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if bytes.len() >= 32 && super::x86::has_avx2() {
+                found.push(collect(&|f| unsafe { super::x86::each_avx2(set, bytes, f) }.unwrap()));
+            }
+            if bytes.len() >= 16 && set.len <= MAX_NEEDLES && super::x86::has_sse2() {
+                found.push(collect(&|f| unsafe { super::x86::each_sse2(set, bytes, f) }.unwrap()));
+            }
+        }
+        // The first position is all `find` needs.
+        assert_eq!(set.find(bytes), found[0].first().cloned());
+        found
+    }
+
+    #[test]
+    fn contains(){
+        for &members in SETS {
+            let set = byte_set(members);
+            for b in 0..=255 {
+                assert_eq!(set.contains(b), members.contains(&b));
+            }
+        }
+    }
+
+    #[test]
+    fn every_position(){
+        let filler = (0..100).map(|i| b"abc\x80\xFF\xC3\xA9z-~"[i % 10]).collect::<Vec<_>>();
+        for &members in SETS {
+            let set = byte_set(members);
+            for len in 0..filler.len() {
+                let mut bytes = filler[..len].to_vec();
+                assert!(searches(&set, &bytes).iter().all(|found| found.is_empty()));
+                for &member in members {
+                    for i in 0..len {
+                        bytes[i] = member;
+                        assert!(searches(&set, &bytes).iter().all(|found| *found == [i]));
+                        if i + 1 < len {
+                            bytes[len - 1] = member;
+                            let expected = [i, len - 1];
+                            assert!(searches(&set, &bytes).iter().all(|found| *found == expected));
+                            bytes[len - 1] = filler[len - 1];
+                        }
+                        bytes[i] = filler[i];
+                    }
+                }
+            }
+        }
+    }
+    #[test]
+    fn every_member(){
+        for &members in SETS {
+            let set = byte_set(members);
+            for len in 0..100 {
+                // Members next to each other and in every position of a word.
+                let bytes = (0..len).map(|i| match members.get(i * 7 % 11) {
+                    Some(&member) if i % 3 != 0 => member,
+                    _ => b'z'
+                }).collect::<Vec<_>>();
+                let expected = (0..len).filter(|&i| set.contains(bytes[i])).collect::<Vec<_>>();
+                assert!(searches(&set, &bytes).iter().all(|found| *found == expected));
+            }
+        }
+    }
+}
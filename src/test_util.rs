@@ -0,0 +1,24 @@
+use std::iter;
+
+
+/// Check that the size hint of the iterator holds before every item it yields, given how many
+/// items it yields in total.
+pub fn check_size_hint<I: Iterator>(mut iter: I, expected: usize) {
+    for consumed in 0..expected + 1 {
+        let (lower, upper) = iter.size_hint();
+        assert!(lower <= expected - consumed && upper.unwrap() >= expected - consumed);
+        assert_eq!(iter.next().is_some(), consumed < expected);
+    }
+}
+
+/// Pseudo-random numbers from a xorshift generator with a fixed seed, so that the tests on random
+/// input are reproducible.
+pub fn random() -> impl Iterator<Item=usize> {
+    let mut state = 0x2545_F491_4F6C_DD1Du64;
+    iter::repeat_with(move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state as usize
+    })
+}
@@ -1,7 +1,13 @@
-use std::char;
-use std::io::Write;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::char;
 
-use unescape_named::LONGEST_NAMED_REFERENCE;
+use entities::{Entities, is_name_byte, is_name_char, is_name_start_byte};
+use error::{UnescapeError, UnescapeErrorKind};
+use scan::ByteSet;
+use unescape_named::Matcher;
+use unescape_named::RefMatch::*;
 
 
 /// Unescape a HTML-encoded stream of bytes.
@@ -12,12 +18,18 @@ use unescape_named::LONGEST_NAMED_REFERENCE;
 /// The implementation works with bytes interpreting them to be ASCII, which means that any
 /// ASCII-compatible encoding, including UTF-8, is supported.
 ///
+/// Numeric references are only kept in memory up to a small length, including their leading
+/// zeros. Longer ones, such as `&#` followed by a megabyte of digits, are decoded like the HTML5
+/// spec prescribes even if they are not terminated with a `;`, usually to U+FFFD as they are out of
+/// range.
+///
 /// [html5-nref]: http://www.w3.org/TR/html5/syntax.html#named-character-references
 #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
 pub struct Unescape<I: Iterator<Item=u8>>{
     inner: I,
+    decoder: Decoder,
     index: usize, // Index into the buffer
-    buffer: Vec<I::Item>,
+    buffer: Vec<u8>,
 }
 
 
@@ -36,191 +48,1185 @@ impl<I: Iterator<Item=u8>> Unescape<I> {
     pub fn new(i: I) -> Unescape<I> {
         Unescape {
             inner: i,
+            decoder: Decoder::new(),
+            index: 0,
+            buffer: Vec::with_capacity(8)
+        }
+    }
+
+    /// Create an iterator adaptor which will unescape the character references found in the
+    /// internal iterator as the HTML5 spec prescribes for attribute values.
+    ///
+    /// In attribute values the named references without the terminating `;` are left alone if
+    /// they are followed by `=` or an alphanumeric character, so that query strings in URLs are
+    /// not mangled.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use marksman_escape::Unescape;
+    /// let string = "/search?q=a&amp;b&copy=2&lang=en&copy";
+    /// let unescaped = String::from_utf8(Unescape::attribute(string.bytes()).collect()).unwrap();
+    /// assert_eq!("/search?q=a&b&copy=2&lang=en©", &*unescaped);
+    /// ```
+    pub fn attribute(i: I) -> Unescape<I> {
+        Unescape {
+            decoder: Decoder::attribute(),
+            ..Unescape::new(i)
+        }
+    }
+
+    /// Create an iterator adaptor which will unescape the character references found in the
+    /// internal iterator as XML 1.0 does.
+    ///
+    /// Only the five predefined entities (`&amp;`, `&lt;`, `&gt;`, `&quot;` and `&apos;`) and the
+    /// numeric references are recognised, and only if they are terminated with a `;`. Everything
+    /// else, including the HTML5 named references such as `&nbsp;`, is left alone and can be
+    /// reported with `report_errors`. Numeric references to code points not allowed in XML 1.0
+    /// documents are replaced with U+FFFD REPLACEMENT CHARACTER.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use marksman_escape::Unescape;
+    /// let string = "&lt;a title=&apos;&nbsp;&#x80;&apos;&gt;&amp";
+    /// let unescaped = String::from_utf8(Unescape::xml(string.bytes()).collect()).unwrap();
+    /// assert_eq!("<a title='&nbsp;\u{80}'>&amp", &*unescaped);
+    /// ```
+    pub fn xml(i: I) -> Unescape<I> {
+        Unescape {
+            decoder: Decoder::xml(),
+            ..Unescape::new(i)
+        }
+    }
+
+    /// Collect the parse errors found in the input.
+    ///
+    /// The HTML5 spec defines a number of parse errors for malformed character references, which
+    /// are otherwise silently handled. The decoded output is the same regardless of whether the
+    /// errors are collected.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use marksman_escape::{Unescape, UnescapeErrorKind};
+    /// let mut unescape = Unescape::new("&amp &#0; &bogus;".bytes()).report_errors();
+    /// let unescaped = String::from_utf8(unescape.by_ref().collect()).unwrap();
+    /// assert_eq!("& \u{FFFD} &bogus;", &*unescaped);
+    /// let errors = unescape.errors().iter().map(|e| (e.offset(), e.kind())).collect::<Vec<_>>();
+    /// assert_eq!(errors, [(0, UnescapeErrorKind::MissingSemicolon),
+    ///                     (5, UnescapeErrorKind::NullCharacter),
+    ///                     (10, UnescapeErrorKind::UnknownNamedReference)]);
+    /// ```
+    pub fn report_errors(mut self) -> Unescape<I> {
+        self.decoder.report_errors();
+        self
+    }
+
+    /// The parse errors found in the input consumed so far, if they are being collected.
+    pub fn errors(&self) -> &[UnescapeError] {
+        self.decoder.errors()
+    }
+
+    /// Recognise the names in the dictionary as well, or instead of the built-in ones if it was
+    /// created with `Entities::exclusive`.
+    ///
+    /// References to names which are neither in the dictionary nor built-in are left alone.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use marksman_escape::{Entities, Unescape};
+    /// let entities = Entities::exclusive().entity("product", "Widget");
+    /// let string = "&product; &amp; &nbsp;";
+    /// let unescaped = String::from_utf8(Unescape::xml(string.bytes()).with_entities(entities)
+    ///                                                                .collect()).unwrap();
+    /// assert_eq!("Widget &amp; &nbsp;", &*unescaped);
+    /// ```
+    pub fn with_entities(mut self, entities: Entities) -> Unescape<I> {
+        self.decoder.set_entities(entities);
+        self
+    }
+
+    /// Decode the reference after a `&`, reading it straight from the inner iterator if it is a
+    /// built-in name, like most references are.
+    ///
+    /// Any other reference is passed to the decoder byte by byte, which is slower.
+    #[inline(never)]
+    fn unescape_named(&mut self) {
+        // References following each other without the `;` are read here too.
+        'reference: loop {
+            // Longer than any built-in name with its `;`.
+            let mut read = [0u8; 32];
+            let mut len = 0;
+            let mut matcher = Matcher::new();
+            let mut longest = None;
+            let mut end = false;
+            while len < read.len() {
+                let byte = match self.inner.next() {
+                    Some(byte) => byte,
+                    None => {
+                        end = true;
+                        break;
+                    }
+                };
+                read[len] = byte;
+                len += 1;
+                match matcher.feed_byte(byte) {
+                    Match(m) if byte == b';' => {
+                        self.buffer.extend_from_slice(m);
+                        self.decoder.offset += len + 1;
+                        return;
+                    },
+                    Partial if byte.is_ascii_alphanumeric() => {},
+                    // A longer name might still match, e.g. `&notin;` rather than `&not`.
+                    Match(m) if byte.is_ascii_alphanumeric() => longest = Some((len, m)),
+                    _ => match longest {
+                        // Without the `;` the longest matching name is replaced, except in
+                        // attribute values, where that depends on the byte after it.
+                        Some((matched, m)) if self.decoder.mode == Mode::Html => {
+                            self.decoder.start = self.decoder.offset;
+                            let end = self.decoder.start + 1 + matched;
+                            self.decoder.error(UnescapeErrorKind::MissingSemicolon, end);
+                            self.buffer.extend_from_slice(m);
+                            self.buffer.extend_from_slice(&read[matched..len - 1]);
+                            self.decoder.offset += len;
+                            if byte == b'&' {
+                                continue 'reference;
+                            }
+                            self.decoder.push(byte, &mut self.buffer);
+                            return;
+                        },
+                        _ => break
+                    }
+                }
+            }
+            self.decoder.push(b'&', &mut self.buffer);
+            for &byte in &read[..len] {
+                self.decoder.push(byte, &mut self.buffer);
+            }
+            if end {
+                self.decoder.finish(&mut self.buffer);
+            }
+            return;
+        }
+    }
+}
+
+
+/// Unescape a HTML-encoded stream of characters.
+///
+/// Same as `Unescape`, except that it works with characters rather than bytes, and yields the
+/// decoded character references as characters directly. The offsets of the reported parse errors
+/// count the bytes of the input encoded as UTF-8.
+///
+/// # Usage
+///
+/// ```
+/// use marksman_escape::UnescapeChars;
+/// let string = "«&lt;hello&gt;&amp;wörld&#60;/hello&#x3e;»";
+/// let unescaped = UnescapeChars::new(string.chars()).collect::<String>();
+/// assert_eq!("«<hello>&wörld</hello>»", &*unescaped);
+/// ```
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct UnescapeChars<I: Iterator<Item=char>>{
+    inner: I,
+    decoder: Decoder,
+    index: usize, // Index into the buffer
+    buffer: Vec<char>,
+}
+
+impl<I: Iterator<Item=char>> UnescapeChars<I> {
+    /// Create an iterator adaptor which will unescape all the character references found in the
+    /// internal iterator.
+    pub fn new(i: I) -> UnescapeChars<I> {
+        UnescapeChars {
+            inner: i,
+            decoder: Decoder::new(),
             index: 0,
             buffer: Vec::with_capacity(8)
         }
     }
 
-    /// Unsafely read a character from the buffer
+    /// Create an iterator adaptor which will unescape the character references found in the
+    /// internal iterator as the HTML5 spec prescribes for attribute values, like
+    /// `Unescape::attribute` does.
+    pub fn attribute(i: I) -> UnescapeChars<I> {
+        UnescapeChars {
+            decoder: Decoder::attribute(),
+            ..UnescapeChars::new(i)
+        }
+    }
+
+    /// Create an iterator adaptor which will unescape the character references found in the
+    /// internal iterator as XML 1.0 does, like `Unescape::xml` does.
+    pub fn xml(i: I) -> UnescapeChars<I> {
+        UnescapeChars {
+            decoder: Decoder::xml(),
+            ..UnescapeChars::new(i)
+        }
+    }
+
+    /// Collect the parse errors found in the input, like `Unescape::report_errors` does.
+    pub fn report_errors(mut self) -> UnescapeChars<I> {
+        self.decoder.report_errors();
+        self
+    }
+
+    /// The parse errors found in the input consumed so far, if they are being collected.
+    pub fn errors(&self) -> &[UnescapeError] {
+        self.decoder.errors()
+    }
+
+    /// Recognise the names in the dictionary as well, like `Unescape::with_entities` does.
+    pub fn with_entities(mut self, entities: Entities) -> UnescapeChars<I> {
+        self.decoder.set_entities(entities);
+        self
+    }
+}
+
+
+/// Unescape a HTML-encoded byte stream which arrives in chunks, such as from a socket.
+///
+/// Unlike `Unescape`, the decoder does not own the source of the input, it is fed the chunks as
+/// they come instead. A character reference may be split across chunks: its beginning is kept
+/// until the rest of it is fed or the input is finished, so the output is the same as `Unescape`
+/// would produce for all of the chunks concatenated.
+///
+/// # Usage
+///
+/// ```
+/// use marksman_escape::StreamingUnescaper;
+/// let mut unescaper = StreamingUnescaper::new();
+/// let mut unescaped = Vec::new();
+/// unescaper.feed(b"&lt;hello&g", &mut unescaped);
+/// assert_eq!(unescaped, b"<hello");
+/// unescaper.feed(b"t;&amp", &mut unescaped);
+/// unescaper.finish(&mut unescaped);
+/// assert_eq!(unescaped, b"<hello>&");
+/// ```
+pub struct StreamingUnescaper {
+    decoder: Decoder,
+}
+
+impl StreamingUnescaper {
+    /// Create a decoder which unescapes the same character references as `Unescape::new` does.
+    pub fn new() -> StreamingUnescaper {
+        StreamingUnescaper {
+            decoder: Decoder::new(),
+        }
+    }
+
+    /// Create a decoder which unescapes the character references as `Unescape::attribute` does.
+    pub fn attribute() -> StreamingUnescaper {
+        StreamingUnescaper {
+            decoder: Decoder::attribute(),
+        }
+    }
+
+    /// Create a decoder which unescapes the character references as `Unescape::xml` does.
+    pub fn xml() -> StreamingUnescaper {
+        StreamingUnescaper {
+            decoder: Decoder::xml(),
+        }
+    }
+
+    /// Collect the parse errors found in the input, like `Unescape::report_errors` does.
+    pub fn report_errors(mut self) -> StreamingUnescaper {
+        self.decoder.report_errors();
+        self
+    }
+
+    /// The parse errors found in the input fed so far, if they are being collected.
+    ///
+    /// The offsets count the bytes of all the chunks fed so far.
+    pub fn errors(&self) -> &[UnescapeError] {
+        self.decoder.errors()
+    }
+
+    /// Recognise the names in the dictionary, like `Unescape::with_entities` does.
+    pub fn with_entities(mut self, entities: Entities) -> StreamingUnescaper {
+        self.decoder.set_entities(entities);
+        self
+    }
+
+    /// Unescape the next chunk of the input, appending the output to `out`.
+    ///
+    /// A character reference at the end of the chunk is kept until it is known where it ends.
+    pub fn feed(&mut self, input: &[u8], out: &mut Vec<u8>) {
+        self.decoder.feed(input, out);
+    }
+
+    /// Finish the input, appending the output for the character reference at its end, if any.
+    ///
+    /// Returns the parse errors found in all of the input, if they are being collected.
+    pub fn finish(mut self, out: &mut Vec<u8>) -> Vec<UnescapeError> {
+        self.decoder.finish(out);
+        self.decoder.into_errors()
+    }
+}
+
+impl Default for StreamingUnescaper {
+    fn default() -> StreamingUnescaper {
+        StreamingUnescaper::new()
+    }
+}
+
+/// Length of the longest numeric reference which is emitted unchanged when it is not terminated by
+/// a `;`.
+pub(crate) const LONGEST_BUFFERED_NUMERIC_REFERENCE : usize = 16;
+
+/// Which references the decoder recognises.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Html,
+    HtmlAttribute,
+    Xml,
+}
+
+/// Names of the XML predefined entities and their replacements.
+static XML_ENTITIES: [(&[u8], &[u8]); 5] = [
+    (b"amp", b"&"), (b"lt", b"<"), (b"gt", b">"), (b"quot", b"\""), (b"apos", b"'"),
+];
+
+/// Length of the longest XML predefined entity name.
+const LONGEST_XML_ENTITY : usize = 4;
+
+/// The byte which starts every character reference, for runs of text to be skipped in bulk.
+static AMPERSAND: ByteSet = ByteSet::single(b'&');
+
+/// Where in a character reference the decoder is.
+#[derive(Clone, Copy)]
+enum State {
+    /// Not inside of a character reference.
+    Text,
+    /// After `&`.
+    Ampersand,
+    /// After `&#`.
+    Numeric,
+    /// Inside of a decimal reference, with the value decoded so far.
+    Dec(u32),
+    /// Inside of a hexadecimal reference, with the value decoded so far.
+    Hex(u32),
+    /// Inside of a named reference, which is matched against the built-in names as it is read.
+    Named,
+    /// Inside of a named reference which is looked up in a dictionary once the `;` is read.
+    Dictionary,
+    /// Inside of a run of alphanumeric characters after `&` which did not match any name. The
+    /// characters are emitted unchanged, but a `;` at the end makes it an unknown reference.
+    Ambiguous,
+}
+
+/// Push-based decoder of character references.
+///
+/// The decoder keeps the state of a partially read character reference between calls, so the
+/// input may be split at arbitrary points.
+pub struct Decoder {
+    state: State,
+    mode: Mode,
+    /// Names recognised in addition to, or instead of, the built-in ones.
+    entities: Option<Entities>,
+    /// Where in the built-in names the named reference being read is.
+    matcher: Matcher,
+    /// Length of the pending bytes at the longest built-in name matched so far, and its
+    /// replacement.
+    longest: Option<(usize, &'static [u8])>,
+    /// The bytes of the reference read so far, which are emitted unchanged if the reference
+    /// turns out to be invalid.
+    pending: Vec<u8>,
+    /// Whether digits of the numeric reference being read were left out of `pending` because it
+    /// grew too long.
+    truncated: bool,
+    /// Number of bytes consumed so far.
+    offset: usize,
+    /// Offset of the `&` which started the current reference.
+    start: usize,
+    /// Parse errors found so far, if they are being collected.
+    errors: Option<Vec<UnescapeError>>,
+    /// Whether alphanumeric runs after `&` which are not references are reported as unknown
+    /// names even without the terminating `;`, and numeric references are only reported when
+    /// they decode to U+FFFD.
+    strict: bool,
+}
+
+/// Where the decoder writes its output.
+pub trait Output {
+    /// Append a byte of the input passed through unchanged.
+    fn push_byte(&mut self, byte: u8);
+    /// Append bytes of the input passed through unchanged, or a replacement, as UTF-8.
+    fn push_bytes(&mut self, bytes: &[u8]);
+    /// Append a decoded character.
+    fn push_char(&mut self, ch: char);
+}
+
+impl Output for Vec<u8> {
     #[inline]
-    fn unext(&mut self) -> u8 {
-        let r = unsafe { *(*self.buffer).get_unchecked(self.index) };
-        self.index += 1;
-        r
+    fn push_byte(&mut self, byte: u8) {
+        self.push(byte);
     }
 
-    /// Drop the contents from the buffer.
     #[inline]
-    fn drop_buffer(&mut self) {
-        unsafe {
-            self.buffer.set_len(0);
-        }
+    fn push_bytes(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
     }
 
-    /// Read the next character from internal iterator adding the byte into buffer.
     #[inline]
-    fn read_to_buffer(&mut self) -> Option<u8> {
-        if let Some(x) = self.inner.next() {
-            self.buffer.push(x);
-            Some(x)
-        } else {
-            None
+    fn push_char(&mut self, ch: char) {
+        let mut utf8 = [0u8; 4];
+        self.extend_from_slice(ch.encode_utf8(&mut utf8).as_bytes());
+    }
+}
+
+impl Output for Vec<char> {
+    #[inline]
+    fn push_byte(&mut self, byte: u8) {
+        self.push(byte as char);
+    }
+
+    fn push_bytes(&mut self, bytes: &[u8]) {
+        self.extend(String::from_utf8_lossy(bytes).chars());
+    }
+
+    #[inline]
+    fn push_char(&mut self, ch: char) {
+        self.push(ch);
+    }
+}
+
+impl Decoder {
+    pub fn new() -> Decoder {
+        Decoder {
+            state: State::Text,
+            mode: Mode::Html,
+            entities: None,
+            matcher: Matcher::new(),
+            longest: None,
+            pending: Vec::with_capacity(8),
+            truncated: false,
+            offset: 0,
+            start: 0,
+            errors: None,
+            strict: false,
         }
     }
 
-    fn from_codepoint(&mut self, codepoint: u32) -> u8 {
-        self.drop_buffer();
-        self.index = 0;
-        let string = if codepoint & 0xfffe == 0xfffe {
-            "\u{FFFD}"
-        } else {
-            match codepoint {
-                0x80 => "\u{20AC}",
-                0x82 => "\u{201A}",
-                0x83 => "\u{0192}",
-                0x84 => "\u{201E}",
-                0x85 => "\u{2026}",
-                0x86 => "\u{2020}",
-                0x87 => "\u{2021}",
-                0x88 => "\u{02C6}",
-                0x89 => "\u{2030}",
-                0x8A => "\u{0160}",
-                0x8B => "\u{2039}",
-                0x8C => "\u{0152}",
-                0x8E => "\u{017D}",
-                0x91 => "\u{2018}",
-                0x92 => "\u{2019}",
-                0x93 => "\u{201C}",
-                0x94 => "\u{201D}",
-                0x95 => "\u{2022}",
-                0x96 => "\u{2013}",
-                0x97 => "\u{2014}",
-                0x98 => "\u{02DC}",
-                0x99 => "\u{2122}",
-                0x9A => "\u{0161}",
-                0x9B => "\u{203A}",
-                0x9C => "\u{0153}",
-                0x9E => "\u{017E}",
-                0x9F => "\u{0178}",
-                0x000B
-                | 0x0000...0x0008
-                | 0x000D...0x001F
-                | 0x007F...0x009F
-                | 0xFDD0...0xFDEF => "\u{FFFD}",
-                chr  => if let Some(chr) = char::from_u32(chr) {
-                    let mut utf8 = [0u8; 4];
-                    let bytes_written = {
-                        let mut buffer = &mut utf8[..];
-                        write!(buffer, "{}", chr).unwrap();
-                        4 - buffer.len()
-                    };
-                    self.buffer = utf8[..bytes_written].to_vec();
-
-                    return self.unext();
-                } else {
-                    "\u{FFFD}"
+    /// Create a decoder for attribute values.
+    pub fn attribute() -> Decoder {
+        Decoder {
+            mode: Mode::HtmlAttribute,
+            ..Decoder::new()
+        }
+    }
+
+    /// Create a decoder for XML.
+    pub fn xml() -> Decoder {
+        Decoder {
+            mode: Mode::Xml,
+            ..Decoder::new()
+        }
+    }
+
+    /// Start collecting the parse errors.
+    pub fn report_errors(&mut self) {
+        if self.errors.is_none() {
+            self.errors = Some(Vec::new());
+        }
+    }
+
+    /// Recognise the names in the dictionary.
+    pub fn set_entities(&mut self, entities: Entities) {
+        self.entities = Some(entities);
+    }
+
+    /// The parse errors collected so far.
+    pub fn errors(&self) -> &[UnescapeError] {
+        match self.errors {
+            Some(ref errors) => errors,
+            None => &[]
+        }
+    }
+
+    /// Take the parse errors collected so far.
+    pub fn into_errors(self) -> Vec<UnescapeError> {
+        self.errors.unwrap_or_default()
+    }
+
+    /// Upper bound of the length of the output for `len` more bytes of input, including the
+    /// output for the reference read so far.
+    pub fn output_upper_bound(&self, len: usize) -> Option<usize> {
+        let len = len.checked_add(self.pending.len())?;
+        // References are never longer decoded than encoded, except for `&nGt;` and `&nLt;`, which
+        // are longer by a byte. Truncated numeric references are at least as long as `pending`.
+        let mut growth = len / "&nGt;".len();
+        if let Some(ref entities) = self.entities {
+            // The shortest reference to a name from the dictionary is 3 bytes long, e.g. `&a;`.
+            let longer = entities.longest_replacement().saturating_sub("&a;".len());
+            growth = growth.max((len / "&a;".len()).checked_mul(longer)?);
+        }
+        len.checked_add(growth)
+    }
+
+    /// Whether the decoder is not inside of a character reference.
+    #[inline]
+    pub fn is_idle(&self) -> bool {
+        matches!(self.state, State::Text)
+    }
+
+    /// Decode the input, appending the output to `out`.
+    pub fn feed<O: Output>(&mut self, mut input: &[u8], out: &mut O) {
+        while !input.is_empty() {
+            if self.is_idle() {
+                let run = AMPERSAND.find(input).unwrap_or(input.len());
+                out.push_bytes(&input[..run]);
+                self.offset += run;
+                input = &input[run..];
+                if input.is_empty() {
+                    return;
                 }
             }
-        };
-        self.buffer.extend(string.bytes());
-        self.unext()
+            self.push(input[0], out);
+            input = &input[1..];
+        }
     }
 
-    fn unescape_named(&mut self, byte: u8) -> u8 {
-        use unescape_named::Matcher;
-        use unescape_named::RefMatch::*;
-        let mut matcher = Matcher::new();
-        matcher.feed_byte(byte);
-        loop {
-            match self.read_to_buffer() {
-                Some(b';') => match matcher.feed_byte(b';') {
+    /// Decode a single byte, appending the output to `out`.
+    #[inline]
+    pub fn push<O: Output>(&mut self, byte: u8, out: &mut O) {
+        // Most of the bytes of a reference to a built-in name are handled here rather than by
+        // `step`, which is too big to be inlined into the loops calling this.
+        match (self.state, byte) {
+            (State::Text, b'&') => {
+                self.start = self.offset;
+                self.pending.push(byte);
+                self.state = State::Ampersand;
+            },
+            (State::Text, _) => out.push_byte(byte),
+            (State::Ampersand, b'a'..=b'z') | (State::Ampersand, b'A'..=b'Z')
+            if self.builtin_only() => {
+                self.matcher = Matcher::new();
+                self.matcher.feed_byte(byte);
+                self.longest = None;
+                self.pending.push(byte);
+                self.state = State::Named;
+            },
+            (State::Named, _) if byte.is_ascii_alphanumeric() => {
+                match self.matcher.feed_byte(byte) {
+                    Partial => self.pending.push(byte),
                     Match(m) => {
-                        self.drop_buffer();
-                        self.buffer.extend(m);
-                        return self.unext();
+                        self.pending.push(byte);
+                        self.longest = Some((self.pending.len(), m));
                     },
-                    _ => return b'&'
-                },
-                Some(b@b'a'...b'z') | Some(b@b'A'...b'Z') | Some(b@b'0'...b'9') => {
-                    match matcher.feed_byte(b) {
-                        Mismatch   => return b'&',
-                        Partial => continue,
-                        Match(m) => {
-                            self.drop_buffer();
-                            self.buffer.extend(m);
-                            // Check for ; next
-                            let oldl = self.buffer.len();
-                            if let Some(b';') = self.read_to_buffer() {
-                                // If we read the `;`, just get rid of it.
-                                unsafe { self.buffer.set_len(oldl) }
-                            }
-                            return self.unext();
-                        }
-                    }
+                    // The matcher keeps mismatching, so `step` sees the same.
+                    Mismatch => return self.push_slow(byte, out),
+                }
+            },
+            (State::Named, b';') if self.builtin_only() => match self.matcher.feed_byte(b';') {
+                Match(m) => {
+                    self.pending.clear();
+                    out.push_bytes(m);
+                    self.state = State::Text;
                 },
-                _ => return b'&'
+                _ => return self.push_slow(byte, out),
+            },
+            _ => return self.push_slow(byte, out)
+        }
+        self.offset += 1;
+    }
+
+    /// Decode a single byte with the full state machine.
+    fn push_slow<O: Output>(&mut self, byte: u8, out: &mut O) {
+        self.step(byte, out);
+        self.offset += 1;
+    }
+
+    /// Decode a single character, appending the output to `out`.
+    pub fn push_char<O: Output>(&mut self, ch: char, out: &mut O) {
+        if ch.is_ascii() {
+            return self.push(ch as u8, out);
+        }
+        if self.continues_name(ch) {
+            let mut utf8 = [0u8; 4];
+            for &byte in ch.encode_utf8(&mut utf8).as_bytes() {
+                self.push(byte, out);
             }
+            return;
         }
+        // No other reference contains non-ASCII characters, so this one ends the current
+        // reference like the end of input would.
+        self.finish(out);
+        out.push_char(ch);
+        self.offset += ch.len_utf8();
     }
 
-    // TODO: this can be abused to store a lot of characters in memory by providing it
-    // &#<very very long sequence of decimal digits>
-    fn unescape_dec(&mut self, byte: u8) -> u8 {
-        let mut value: u32 = (byte - b'0') as u32;
-        loop {
-            match self.read_to_buffer() {
-                Some(b';') => {// end of a character reference with a valid syntax
-                    return self.from_codepoint(value);
+    fn step<O: Output>(&mut self, byte: u8, out: &mut O) {
+        self.state = match self.state {
+            State::Text => if byte == b'&' {
+                self.start = self.offset;
+                self.pending.push(byte);
+                State::Ampersand
+            } else {
+                out.push_byte(byte);
+                State::Text
+            },
+            State::Ampersand => match byte {
+                b'#' => {
+                    self.pending.push(byte);
+                    State::Numeric
+                },
+                b'a'..=b'z' | b'A'..=b'Z' if self.mode == Mode::Xml || !self.builtin() => {
+                    self.pending.push(byte);
+                    self.longest = None;
+                    State::Dictionary
+                },
+                b'a'..=b'z' | b'A'..=b'Z' => {
+                    self.matcher = Matcher::new();
+                    self.matcher.feed_byte(byte);
+                    self.longest = None;
+                    self.pending.push(byte);
+                    State::Named
+                },
+                _ if is_name_start_byte(byte) && self.entities.is_some() => {
+                    self.pending.push(byte);
+                    self.longest = None;
+                    State::Dictionary
+                },
+                b'0'..=b'9' => { // no name starts with a digit
+                    self.flush_pending(out);
+                    out.push_byte(byte);
+                    State::Ambiguous
+                },
+                _ => return self.reject(byte, out) // not an escape
+            },
+            State::Numeric => match byte {
+                b'x' | b'X' => {
+                    self.pending.push(byte);
+                    State::Hex(0)
                 },
-                Some(b@b'0'...b'9') => if value <= 0x10FFFF {
-                    value = (value * 10) + ((b - b'0') as u32);
+                b'0'..=b'9' => {
+                    self.push_digit(byte);
+                    State::Dec((byte - b'0') as u32)
                 },
-                _ => return b'&' // not an escape
+                _ => return self.end_numeric(Some(byte), out)
+            },
+            State::Dec(value) => match byte {
+                b';' => { // end of a character reference with a valid syntax
+                    self.clear_numeric();
+                    let end = self.offset + 1;
+                    self.decode_codepoint(value, end, out);
+                    State::Text
+                },
+                b'0'..=b'9' => {
+                    self.push_digit(byte);
+                    State::Dec(if value <= 0x10FFFF {
+                        (value * 10) + ((byte - b'0') as u32)
+                    } else {
+                        value
+                    })
+                },
+                _ => return self.end_numeric(Some(byte), out)
+            },
+            State::Hex(value) => {
+                let digit = match byte {
+                    b';' if self.has_digits() => {
+                        self.clear_numeric();
+                        let end = self.offset + 1;
+                        self.decode_codepoint(value, end, out);
+                        self.state = State::Text;
+                        return;
+                    },
+                    b';' if self.mode == Mode::Xml => return self.end_numeric(Some(byte), out),
+                    b';' => {
+                        self.pending.clear();
+                        let end = self.offset + 1;
+                        self.error(UnescapeErrorKind::AbsenceOfDigits, end);
+                        out.push_char('\u{FFFD}');
+                        self.state = State::Text;
+                        return;
+                    },
+                    b'0'..=b'9' => byte - b'0',
+                    b'a'..=b'f' | b'A'..=b'F' => (byte | 0b0010_0000) - b'a' + 10,
+                    _ => return self.end_numeric(Some(byte), out)
+                };
+                self.push_digit(byte);
+                State::Hex(if value <= 0x10FFFF {
+                    (value * 0x10) + (digit as u32)
+                } else {
+                    value
+                })
+            },
+            State::Named => match byte {
+                b';' if self.entities.is_some() && self.lookup(out) => State::Text,
+                b';' => match self.matcher.feed_byte(b';') {
+                    Match(m) => {
+                        self.pending.clear();
+                        out.push_bytes(m);
+                        State::Text
+                    },
+                    _ => return self.end_named(Some(byte), out)
+                },
+                b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' => match self.matcher.feed_byte(byte) {
+                    Partial => {
+                        self.pending.push(byte);
+                        State::Named
+                    },
+                    Match(m) => {
+                        // A longer name might still match, e.g. `&notin;` rather than `&not`.
+                        self.pending.push(byte);
+                        self.longest = Some((self.pending.len(), m));
+                        State::Named
+                    },
+                    Mismatch if self.fits_dictionary(1) => {
+                        self.pending.push(byte);
+                        State::Dictionary
+                    },
+                    Mismatch => return self.end_named(Some(byte), out)
+                },
+                _ if self.continues_name_byte(byte) => {
+                    self.pending.push(byte);
+                    State::Dictionary
+                },
+                _ => return self.end_named(Some(byte), out)
+            },
+            State::Dictionary => match byte {
+                b';' if self.lookup(out) => State::Text,
+                b';' if self.longest.is_some() => return self.end_named(Some(byte), out),
+                b';' => {
+                    let end = self.offset + 1;
+                    self.error(UnescapeErrorKind::UnknownNamedReference, end);
+                    self.flush_pending(out);
+                    out.push_byte(byte);
+                    State::Text
+                },
+                _ if self.continues_name_byte(byte) => {
+                    self.pending.push(byte);
+                    State::Dictionary
+                },
+                _ if self.longest.is_some() => return self.end_named(Some(byte), out),
+                _ => return self.end_dictionary(Some(byte), out)
+            },
+            State::Ambiguous => match byte {
+                b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' => {
+                    out.push_byte(byte);
+                    State::Ambiguous
+                },
+                b';' => {
+                    let end = self.offset + 1;
+                    self.error(UnescapeErrorKind::UnknownNamedReference, end);
+                    out.push_byte(byte);
+                    State::Text
+                },
+                _ => {
+                    self.end_ambiguous();
+                    return self.step(byte, out);
+                }
             }
         }
     }
 
-    // TODO: this can be abused to store a lot of characters in memory by providing it
-    // &#<very very long sequence of hex digits>
-    fn unescape_hex(&mut self) -> u8 {
-        let mut value: u32 = 0;
-        loop {
-            let byte = self.read_to_buffer();
-            if let Some(b';') = byte {
-                return self.from_codepoint(value);
-            } else if let Some(b@b'0'...b'9') = byte {
-                if value <= 0x10FFFF {
-                    value = (value * 0x10) + ((b - b'0') as u32);
+    /// Finish decoding the input, flushing the partially read reference, if any.
+    pub fn finish<O: Output>(&mut self, out: &mut O) {
+        match self.state {
+            State::Numeric | State::Dec(_) | State::Hex(_) => self.end_numeric(None, out),
+            State::Named => self.end_named(None, out),
+            State::Dictionary if self.longest.is_none() => self.end_dictionary(None, out),
+            State::Dictionary => self.end_named(None, out),
+            State::Ambiguous => self.end_ambiguous(),
+            _ => self.flush_pending(out)
+        }
+    }
+
+    /// Emit the pending bytes unchanged and get out of the character reference.
+    fn flush_pending<O: Output>(&mut self, out: &mut O) {
+        out.push_bytes(&self.pending);
+        self.pending.clear();
+        self.state = State::Text;
+    }
+
+    /// Handle the end of a named reference without the terminating `;`, caused by `byte`.
+    ///
+    /// The longest matching name is replaced and the rest of the name is emitted unchanged. In
+    /// attribute values the name is not replaced if it is followed by `=` or an alphanumeric
+    /// character, so that e.g. `?a=1&copy=2` survives unescaping.
+    fn end_named<O: Output>(&mut self, byte: Option<u8>, out: &mut O) {
+        if let Some((len, m)) = self.longest {
+            let ambiguous = match self.pending.get(len).cloned().or(byte) {
+                Some(next) => next == b'=' || next.is_ascii_alphanumeric(),
+                None => false
+            };
+            if !(self.mode == Mode::HtmlAttribute && ambiguous) {
+                let end = self.start + len;
+                self.error(UnescapeErrorKind::MissingSemicolon, end);
+                out.push_bytes(m);
+                self.pending.drain(..len);
+            }
+            self.flush_pending(out);
+        } else {
+            self.flush_pending(out);
+            // The rest of the alphanumeric run is not a reference either.
+            self.state = State::Ambiguous;
+        }
+        match byte {
+            Some(byte) => self.step(byte, out),
+            None => self.finish(out)
+        }
+    }
+
+    /// Handle the end of a run of alphanumeric characters which is not a reference, without the
+    /// terminating `;`.
+    fn end_ambiguous(&mut self) {
+        if self.strict {
+            let end = self.offset;
+            self.error(UnescapeErrorKind::UnknownNamedReference, end);
+        }
+        self.state = State::Text;
+    }
+
+    /// Whether the named references are only matched against the built-in names.
+    #[inline]
+    fn builtin_only(&self) -> bool {
+        self.mode != Mode::Xml && self.entities.is_none()
+    }
+
+    /// Whether the built-in names are recognised.
+    #[inline]
+    fn builtin(&self) -> bool {
+        self.entities.as_ref().is_none_or(Entities::builtin)
+    }
+
+    /// Whether the name being read can grow by `len` more bytes and still be looked up once the
+    /// `;` is read.
+    fn fits_dictionary(&self, len: usize) -> bool {
+        let mut longest = self.entities.as_ref().map_or(0, Entities::longest);
+        if self.mode == Mode::Xml && self.builtin() {
+            longest = longest.max(LONGEST_XML_ENTITY);
+        }
+        // The pending bytes start with the `&`.
+        self.pending.len() + len <= longest + 1
+    }
+
+    /// Whether the byte continues a name which is looked up in the dictionary or the XML
+    /// predefined entities.
+    ///
+    /// Like in `continues_name`, non-ASCII bytes only continue names from a dictionary.
+    fn continues_name_byte(&self, byte: u8) -> bool {
+        is_name_byte(byte) && (byte.is_ascii() || self.entities.is_some()) &&
+            self.fits_dictionary(1)
+    }
+
+    /// Whether the non-ASCII character continues a name which is looked up in the dictionary.
+    fn continues_name(&self, ch: char) -> bool {
+        match self.state {
+            State::Ampersand | State::Named | State::Dictionary => {},
+            _ => return false
+        }
+        // The non-ASCII characters which may start a name are a subset of the ones which may
+        // appear in a name, and the dictionary only holds valid names anyway.
+        self.entities.is_some() && is_name_char(ch) && self.fits_dictionary(ch.len_utf8())
+    }
+
+    /// Replacement of the pending name in the dictionary or the XML predefined entities.
+    fn find(&self) -> Option<&[u8]> {
+        let name = &self.pending[1..];
+        if let Some(replacement) = self.entities.as_ref().and_then(|e| e.get(name)) {
+            return Some(replacement);
+        }
+        if self.mode == Mode::Xml && self.builtin() {
+            XML_ENTITIES.iter().find(|&&(n, _)| n == name).map(|&(_, replacement)| replacement)
+        } else {
+            None
+        }
+    }
+
+    /// Emit the replacement of the pending name and get out of the reference, if the name is found
+    /// in the dictionary or the XML predefined entities.
+    fn lookup<O: Output>(&mut self, out: &mut O) -> bool {
+        match self.find() {
+            Some(replacement) => out.push_bytes(replacement),
+            None => return false
+        }
+        self.pending.clear();
+        true
+    }
+
+    /// Handle the end of a name looked up in a dictionary without the terminating `;`, caused by
+    /// `byte`.
+    ///
+    /// The `;` is required, so the reference is left alone.
+    fn end_dictionary<O: Output>(&mut self, byte: Option<u8>, out: &mut O) {
+        if self.find().is_some() {
+            let end = self.offset;
+            self.error(UnescapeErrorKind::MissingSemicolon, end);
+        }
+        self.flush_pending(out);
+        // The rest of the alphanumeric run is not a reference either.
+        self.state = State::Ambiguous;
+        match byte {
+            Some(byte) => self.step(byte, out),
+            None => self.finish(out)
+        }
+    }
+
+    /// Whether the hexadecimal reference being read has any digits.
+    #[inline]
+    fn has_digits(&self) -> bool {
+        self.pending.len() > "&#x".len()
+    }
+
+    /// Buffer a digit of the numeric reference being read.
+    ///
+    /// The digits past `LONGEST_BUFFERED_NUMERIC_REFERENCE`, leading zeros included, are dropped,
+    /// so arbitrarily long references do not grow the memory use. Truncated references cannot be
+    /// emitted unchanged when they turn out to be unterminated, so they are decoded regardless of
+    /// the `;`, like the HTML5 spec decodes all the numeric references.
+    #[inline]
+    fn push_digit(&mut self, digit: u8) {
+        if self.pending.len() < LONGEST_BUFFERED_NUMERIC_REFERENCE {
+            self.pending.push(digit);
+        } else {
+            self.truncated = true;
+        }
+    }
+
+    /// Forget the numeric reference read so far.
+    #[inline]
+    fn clear_numeric(&mut self) {
+        self.pending.clear();
+        self.truncated = false;
+    }
+
+    /// Emit the bytes of the numeric reference read so far unchanged and get out of the reference.
+    fn flush_numeric<O: Output>(&mut self, out: &mut O) {
+        self.truncated = false;
+        self.flush_pending(out);
+    }
+
+    /// Handle the end of a numeric reference without the terminating `;`, caused by `byte`.
+    fn end_numeric<O: Output>(&mut self, byte: Option<u8>, out: &mut O) {
+        let end = self.offset;
+        match self.state {
+            State::Hex(_) if !self.has_digits() => {
+                self.error(UnescapeErrorKind::AbsenceOfDigits, end);
+            },
+            State::Dec(value) | State::Hex(value) => {
+                self.error(UnescapeErrorKind::MissingSemicolon, end);
+                if self.truncated {
+                    self.clear_numeric();
+                    self.decode_codepoint(value, end, out);
                 }
-            } else if let Some(b@b'a'...b'f') = byte.map(|x| { x | 0b0010_0000}) {
-                if value <= 0x10FFFF {
-                    value = (value * 16) + ((b - b'a' + 10) as u32);
+            },
+            _ => self.error(UnescapeErrorKind::AbsenceOfDigits, end)
+        }
+        self.flush_numeric(out);
+        if let Some(byte) = byte {
+            self.step(byte, out);
+        }
+    }
+
+    /// Emit the bytes of an invalid reference and continue decoding from the byte which made the
+    /// reference invalid.
+    fn reject<O: Output>(&mut self, byte: u8, out: &mut O) {
+        self.flush_pending(out);
+        self.step(byte, out);
+    }
+
+    /// Decode the numeric reference ending at `end`.
+    fn decode_codepoint<O: Output>(&mut self, codepoint: u32, end: usize, out: &mut O) {
+        if self.mode == Mode::Xml {
+            let error = xml_codepoint_error(codepoint);
+            match (error, char::from_u32(codepoint)) {
+                (None, Some(chr)) => out.push_char(chr),
+                (error, _) => {
+                    self.error(error.unwrap_or(UnescapeErrorKind::OutsideUnicodeRange), end);
+                    out.push_char('\u{FFFD}');
                 }
-            } else {
-                return b'&'; // Not a valid escape sequence
             }
+            return;
         }
+        let chr = codepoint_char(codepoint);
+        if let Some(kind) = codepoint_error(codepoint) {
+            if !self.strict || chr == '\u{FFFD}' {
+                self.error(kind, end);
+            }
+        }
+        out.push_char(chr);
     }
 
+    /// Report an error in the reference ending at `end`.
     #[inline]
-    fn unescape_numerical(&mut self) -> u8 {
-        match self.read_to_buffer() {
-            Some(b'x') | Some(b'X') => self.unescape_hex(),
-            Some(c@b'0'...b'9')     => self.unescape_dec(c),
-            _                       => b'&' // not an escape, return the consumed ampersand
+    fn error(&mut self, kind: UnescapeErrorKind, end: usize) {
+        if let Some(ref mut errors) = self.errors {
+            errors.push(UnescapeError::new(self.start, end - self.start, kind));
         }
     }
+}
 
-    #[inline]
-    fn unescape(&mut self) -> u8 {
-        self.drop_buffer();
-        self.index = 0;
+/// The parse error the HTML5 spec reports for a numeric reference to the code point, if any.
+fn codepoint_error(codepoint: u32) -> Option<UnescapeErrorKind> {
+    Some(match codepoint {
+        0 => UnescapeErrorKind::NullCharacter,
+        0x110000..=0xFFFFFFFF => UnescapeErrorKind::OutsideUnicodeRange,
+        0xD800..=0xDFFF => UnescapeErrorKind::Surrogate,
+        0xFDD0..=0xFDEF => UnescapeErrorKind::Noncharacter,
+        c if c & 0xfffe == 0xfffe => UnescapeErrorKind::Noncharacter,
+        0x09 | 0x0A | 0x0C => return None,
+        0x01..=0x1F | 0x7F..=0x9F => UnescapeErrorKind::ControlCharacter,
+        _ => return None
+    })
+}
+
+/// The error for a numeric reference to the code point in XML, if the code point is not allowed in
+/// XML 1.0 documents.
+fn xml_codepoint_error(codepoint: u32) -> Option<UnescapeErrorKind> {
+    Some(match codepoint {
+        0 => UnescapeErrorKind::NullCharacter,
+        0x110000..=0xFFFFFFFF => UnescapeErrorKind::OutsideUnicodeRange,
+        0xD800..=0xDFFF => UnescapeErrorKind::Surrogate,
+        0xFFFE | 0xFFFF => UnescapeErrorKind::Noncharacter,
+        0x09 | 0x0A | 0x0D => return None,
+        0x01..=0x1F => UnescapeErrorKind::ControlCharacter,
+        _ => return None
+    })
+}
+
+/// The character a numeric reference to the code point decodes to.
+fn codepoint_char(codepoint: u32) -> char {
+    if codepoint & 0xfffe == 0xfffe {
+        '\u{FFFD}'
+    } else {
+        match codepoint {
+            0x80 => '\u{20AC}',
+            0x82 => '\u{201A}',
+            0x83 => '\u{0192}',
+            0x84 => '\u{201E}',
+            0x85 => '\u{2026}',
+            0x86 => '\u{2020}',
+            0x87 => '\u{2021}',
+            0x88 => '\u{02C6}',
+            0x89 => '\u{2030}',
+            0x8A => '\u{0160}',
+            0x8B => '\u{2039}',
+            0x8C => '\u{0152}',
+            0x8E => '\u{017D}',
+            0x91 => '\u{2018}',
+            0x92 => '\u{2019}',
+            0x93 => '\u{201C}',
+            0x94 => '\u{201D}',
+            0x95 => '\u{2022}',
+            0x96 => '\u{2013}',
+            0x97 => '\u{2014}',
+            0x98 => '\u{02DC}',
+            0x99 => '\u{2122}',
+            0x9A => '\u{0161}',
+            0x9B => '\u{203A}',
+            0x9C => '\u{0153}',
+            0x9E => '\u{017E}',
+            0x9F => '\u{0178}',
+            0x000B
+            | 0x0000..=0x0008
+            | 0x000D..=0x001F
+            | 0x007F..=0x009F
+            | 0xFDD0..=0xFDEF => '\u{FFFD}',
+            chr => char::from_u32(chr).unwrap_or('\u{FFFD}')
+        }
+    }
+}
+
+/// Unescape a HTML-encoded byte slice.
+///
+/// The slice is returned as is if it contains no character references.
+///
+/// # Usage
+///
+/// ```
+/// use marksman_escape::unescape_bytes;
+/// assert_eq!(&*unescape_bytes(b"&lt;hello&gt;"), b"<hello>");
+/// ```
+pub fn unescape_bytes<'a>(bytes: &'a [u8]) -> Cow<'a, [u8]> {
+    let first = match AMPERSAND.find(bytes) {
+        Some(first) => first,
+        None => return Cow::Borrowed(bytes)
+    };
+    let mut out = Vec::with_capacity(unescaped_len_upper_bound(bytes));
+    let mut decoder = Decoder::new();
+    out.extend_from_slice(&bytes[..first]);
+    decoder.feed(&bytes[first..], &mut out);
+    decoder.finish(&mut out);
+    Cow::Owned(out)
+}
 
-        match self.read_to_buffer() {
-            Some(b'#') => self.unescape_numerical(),
-            Some(c@b'a'...b'z') | Some(c@b'A'...b'Z') => self.unescape_named(c),
-            _ => b'&' // not an escape, return the consumed ampersand
+/// Upper bound of the length of the byte slice after unescaping.
+///
+/// Character references are never longer decoded than encoded, except for `&nGt;` and `&nLt;`,
+/// which are counted in one pass over the slice. `unescape_bytes` allocates this many bytes, so the
+/// output is never reallocated.
+///
+/// # Usage
+///
+/// ```
+/// use marksman_escape::unescaped_len_upper_bound;
+/// assert_eq!(unescaped_len_upper_bound(b"&lt;hello&gt;"), 13);
+/// assert_eq!(unescaped_len_upper_bound(b"a &nGt; b"), 10);
+/// ```
+pub fn unescaped_len_upper_bound(bytes: &[u8]) -> usize {
+    let mut longer = 0;
+    AMPERSAND.for_each(bytes, |i| {
+        let rest = &bytes[i + 1..];
+        if rest.starts_with(b"nGt;") || rest.starts_with(b"nLt;") {
+            longer += 1;
         }
+    });
+    bytes.len() + longer
+}
+
+/// Unescape a HTML-encoded string slice.
+///
+/// The slice is returned as is if it contains no character references.
+///
+/// # Usage
+///
+/// ```
+/// use marksman_escape::unescape_str;
+/// assert_eq!(unescape_str("&lt;hello&gt;&amp;world&#60;/hello&#x3e;"), "<hello>&world</hello>");
+/// ```
+pub fn unescape_str<'a>(string: &'a str) -> Cow<'a, str> {
+    match unescape_bytes(string.as_bytes()) {
+        Cow::Borrowed(_) => Cow::Borrowed(string),
+        // Bytes outside of character references are passed through unchanged and character
+        // references are always replaced with valid UTF-8, so the output is valid UTF-8 as well.
+        Cow::Owned(v) => Cow::Owned(unsafe { String::from_utf8_unchecked(v) })
+    }
+}
+
+/// Unescape a HTML-encoded byte slice, failing on the first malformed character reference.
+///
+/// Unlike `Unescape`, which decodes malformed references the way browsers do, this fails on
+/// unterminated references, unknown names, missing `;` and numeric references to code points
+/// which are replaced with U+FFFD, such as U+0000 or surrogates. References to the other code
+/// points `Unescape::report_errors` reports, such as `&#x80;` which decodes to `€`, are accepted.
+///
+/// A `&` followed by an alphanumeric character starts a reference, so `&bogus` and `&am` fail as
+/// unknown names even without a `;`. A `&` which does not start a reference, such as in `a & b`,
+/// is not an error.
+///
+/// # Usage
+///
+/// ```
+/// use marksman_escape::{strict_unescape, UnescapeErrorKind};
+/// assert_eq!(strict_unescape(b"&lt;a&gt; &amp; b").unwrap(), b"<a> & b");
+/// let error = strict_unescape(b"&lt;a&gt; &amp b").unwrap_err();
+/// assert_eq!((error.offset(), error.len()), (10, 4));
+/// assert_eq!(error.kind(), UnescapeErrorKind::MissingSemicolon);
+/// ```
+pub fn strict_unescape(bytes: &[u8]) -> Result<Vec<u8>, UnescapeError> {
+    let mut out = Vec::with_capacity(unescaped_len_upper_bound(bytes));
+    let mut decoder = Decoder::new();
+    decoder.report_errors();
+    decoder.strict = true;
+    decoder.feed(bytes, &mut out);
+    decoder.finish(&mut out);
+    match decoder.errors().first() {
+        Some(&error) => Err(error),
+        None => Ok(out)
     }
 }
 
@@ -228,35 +1234,398 @@ impl<I: Iterator<Item=u8>> Iterator for Unescape<I> {
     type Item = u8;
 
     fn next(&mut self) -> Option<u8> {
-        match {if self.index < self.buffer.len() {Some(self.unext())} else {self.inner.next()}} {
-            Some(b'&') => Some(self.unescape()),
-            r@_        => r
+        loop {
+            if self.index < self.buffer.len() {
+                self.index += 1;
+                return Some(self.buffer[self.index - 1]);
+            }
+            let byte = self.inner.next();
+            match byte {
+                Some(b) if b != b'&' && self.decoder.is_idle() => {
+                    self.decoder.offset += 1;
+                    return Some(b);
+                },
+                _ => {}
+            }
+            self.buffer.clear();
+            self.index = 0;
+            match byte {
+                Some(b'&') if self.decoder.is_idle() && self.decoder.builtin_only() => {
+                    self.unescape_named();
+                },
+                Some(b) => self.decoder.push(b, &mut self.buffer),
+                None => {
+                    self.decoder.finish(&mut self.buffer);
+                    if self.buffer.is_empty() {
+                        return None;
+                    }
+                }
+            }
         }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         let (l, u) = self.inner.size_hint();
-        // No numerical reference can be longer than 32, which is the length of longest known
-        // named reference.
-        (l / LONGEST_NAMED_REFERENCE, u)
+        size_hint(&self.decoder, self.buffer.len() - self.index, l, u)
+    }
+}
+
+/// The size hint of an unescaping iterator with `buffered` items of output not yet yielded, and
+/// the size hint `l` and `u` of the inner iterator.
+///
+/// Every reference, however long, decodes to at least one item, so the lower bound cannot be
+/// more than one item beyond the buffered ones. The bound on the bytes of output is a bound on
+/// characters too, since no reference decodes to more characters than it has.
+fn size_hint(decoder: &Decoder, buffered: usize, l: usize, u: Option<usize>)
+             -> (usize, Option<usize>) {
+    let rest = (l > 0 || !decoder.is_idle()) as usize;
+    let upper = u.and_then(|u_| decoder.output_upper_bound(u_))
+                 .and_then(|u_| u_.checked_add(buffered));
+    (buffered.saturating_add(rest), upper)
+}
+
+impl<I: Iterator<Item=char>> Iterator for UnescapeChars<I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        loop {
+            if self.index < self.buffer.len() {
+                self.index += 1;
+                return Some(self.buffer[self.index - 1]);
+            }
+            self.buffer.clear();
+            self.index = 0;
+            match self.inner.next() {
+                Some(c) if c != '&' && self.decoder.is_idle() => {
+                    self.decoder.offset += c.len_utf8();
+                    return Some(c);
+                },
+                Some(c) => self.decoder.push_char(c, &mut self.buffer),
+                None => {
+                    self.decoder.finish(&mut self.buffer);
+                    if self.buffer.is_empty() {
+                        return None;
+                    }
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (l, u) = self.inner.size_hint();
+        size_hint(&self.decoder, self.buffer.len() - self.index, l, u)
     }
 }
 
 
 #[cfg(test)]
 mod test {
+    use std::borrow::Cow;
+    use std::iter;
+    use std::prelude::v1::*;
     use std::str;
-    use ::Unescape;
+    use ::{StreamingUnescaper, Unescape, UnescapeChars, strict_unescape, unescape_str};
+    use super::LONGEST_BUFFERED_NUMERIC_REFERENCE;
+    use test_util::{check_size_hint, random};
     fn run_test(from: &str, to: &str) {
         let dv = Unescape::new(from.bytes()).collect::<Vec<_>>();
-        let d = str::from_utf8(&*dv).unwrap();
+        let d = str::from_utf8(&dv).unwrap();
         assert_eq!(d, to);
+        assert_eq!(unescape_str(from), to);
+        assert_eq!(UnescapeChars::new(from.chars()).collect::<String>(), to);
     }
     #[test]
     fn no_escapes(){
         run_test("Hello world!", "Hello world!");
     }
 
+    #[test]
+    fn attribute_escape(){
+        let run_test = |from: &str, to: &str| {
+            let dv = Unescape::attribute(from.bytes()).collect::<Vec<_>>();
+            assert_eq!(str::from_utf8(&dv).unwrap(), to);
+        };
+        run_test("&amp;&amp", "&&");
+        run_test("?a=1&copy=2", "?a=1&copy=2");
+        run_test("?a=1&copy;=2", "?a=1©=2");
+        run_test("&copyright", "&copyright");
+        run_test("&copy-right", "©-right");
+        run_test("&notit;", "&notit;");
+        run_test("&notin;", "∉");
+        run_test("&not", "¬");
+        run_test("&lang=en", "&lang=en");
+    }
+
+    #[test]
+    fn errors(){
+        use ::UnescapeErrorKind::*;
+        let run_test = |from: &str, to: &str, errors: &[(usize, ::UnescapeErrorKind)]| {
+            let mut unescape = Unescape::new(from.bytes()).report_errors();
+            let dv = unescape.by_ref().collect::<Vec<_>>();
+            assert_eq!(str::from_utf8(&dv).unwrap(), to);
+            let found = unescape.errors().iter().map(|e| (e.offset(), e.kind()));
+            assert_eq!(found.collect::<Vec<_>>(), errors);
+        };
+        run_test("&amp;&lt;&#60;&#x3c;", "&<<<", &[]);
+        run_test("a & b &; &#", "a & b &; &#", &[(9, AbsenceOfDigits)]);
+        run_test("&#; &#x; &#xg", "&#; \u{FFFD} &#xg",
+                 &[(0, AbsenceOfDigits), (4, AbsenceOfDigits), (9, AbsenceOfDigits)]);
+        run_test("&amp &notit; &#60 &#x3c", "& ¬it; &#60 &#x3c",
+                 &[(0, MissingSemicolon), (5, MissingSemicolon), (13, MissingSemicolon),
+                   (18, MissingSemicolon)]);
+        run_test("&bogus; &1; &bogus", "&bogus; &1; &bogus",
+                 &[(0, UnknownNamedReference), (8, UnknownNamedReference)]);
+        run_test("&#0;&#x110000;&#xD800;&#xFFFE;&#xFDD0;",
+                 "\u{FFFD}\u{FFFD}\u{FFFD}\u{FFFD}\u{FFFD}",
+                 &[(0, NullCharacter), (4, OutsideUnicodeRange), (14, Surrogate),
+                   (22, Noncharacter), (30, Noncharacter)]);
+        run_test("&#x80;&#x0B;&#x0D;&#x0A;&#x7F;", "€\u{FFFD}\u{FFFD}\n\u{FFFD}",
+                 &[(0, ControlCharacter), (6, ControlCharacter), (12, ControlCharacter),
+                   (24, ControlCharacter)]);
+        run_test("&#99999999999999999999$", "\u{FFFD}$",
+                 &[(0, MissingSemicolon), (0, OutsideUnicodeRange)]);
+        run_test("&#000000000000000000065$", "A$", &[(0, MissingSemicolon)]);
+        run_test("?a=1&copy=2", "?a=1©=2", &[(4, MissingSemicolon)]);
+
+        let mut unescape = Unescape::attribute("?a=1&copy=2".bytes()).report_errors();
+        assert_eq!(unescape.by_ref().count(), 11);
+        assert!(unescape.errors().is_empty());
+    }
+
+    #[test]
+    fn strict(){
+        use ::UnescapeErrorKind::*;
+        let run_test = |from: &str, reference: &str, kind: ::UnescapeErrorKind| {
+            let error = strict_unescape(from.as_bytes()).unwrap_err();
+            assert_eq!(&from[error.offset()..error.offset() + error.len()], reference);
+            assert_eq!(error.kind(), kind);
+        };
+        assert_eq!(strict_unescape(b"a & b &; &&amp;").unwrap(), b"a & b &; &&");
+        assert_eq!(strict_unescape(b"&#x2E2E;&#33;&notin;").unwrap(), "⸮!∉".as_bytes());
+        run_test("a &amp b", "&amp", MissingSemicolon);
+        run_test("&notit;", "&not", MissingSemicolon);
+        run_test("&#123$", "&#123", MissingSemicolon);
+        run_test("&lt;&#123", "&#123", MissingSemicolon);
+        run_test("&bogus;", "&bogus;", UnknownNamedReference);
+        run_test("x &bogus y", "&bogus", UnknownNamedReference);
+        run_test("x &am", "&am", UnknownNamedReference);
+        run_test("&1x;", "&1x;", UnknownNamedReference);
+        run_test("&#;", "&#", AbsenceOfDigits);
+        run_test("&#x;", "&#x;", AbsenceOfDigits);
+        run_test("&#0;", "&#0;", NullCharacter);
+        run_test("&#xD800;", "&#xD800;", Surrogate);
+        run_test("&#1114112;", "&#1114112;", OutsideUnicodeRange);
+        run_test("&#xFFFF;", "&#xFFFF;", Noncharacter);
+        run_test("&#x1F;", "&#x1F;", ControlCharacter);
+        run_test("&#x80;&#x7F;", "&#x7F;", ControlCharacter);
+        assert_eq!(strict_unescape(b"&#x80;&#x99;&#128;").unwrap(), "€™€".as_bytes());
+        assert_eq!(strict_unescape(b"1 &amp 2").unwrap_err().to_string(),
+                   "missing semicolon after character reference at bytes 2..6");
+    }
+
+    #[test]
+    fn xml_escape(){
+        use ::UnescapeErrorKind::*;
+        let run_test = |from: &str, to: &str, errors: &[(usize, ::UnescapeErrorKind)]| {
+            let mut unescape = Unescape::xml(from.bytes()).report_errors();
+            let dv = unescape.by_ref().collect::<Vec<_>>();
+            assert_eq!(str::from_utf8(&dv).unwrap(), to);
+            let found = unescape.errors().iter().map(|e| (e.offset(), e.kind()));
+            assert_eq!(found.collect::<Vec<_>>(), errors);
+        };
+        run_test("&amp;&lt;&gt;&quot;&apos;", "&<>\"'", &[]);
+        run_test("&#38;&#x26;&#x80;&#xD;", "&&\u{80}\r", &[]);
+        run_test("&AMP;&nbsp;&amplifier;", "&AMP;&nbsp;&amplifier;",
+                 &[(0, UnknownNamedReference), (5, UnknownNamedReference),
+                   (11, UnknownNamedReference)]);
+        run_test("&amp &lt&gt", "&amp &lt&gt",
+                 &[(0, MissingSemicolon), (5, MissingSemicolon), (8, MissingSemicolon)]);
+        run_test("&#x; &#60 &#;", "&#x; &#60 &#;",
+                 &[(0, AbsenceOfDigits), (5, MissingSemicolon), (10, AbsenceOfDigits)]);
+        run_test("&#0;&#x1;&#xFFFE;&#xD800;&#x110000;", "\u{FFFD}\u{FFFD}\u{FFFD}\u{FFFD}\u{FFFD}",
+                 &[(0, NullCharacter), (4, ControlCharacter), (9, Noncharacter),
+                   (17, Surrogate), (25, OutsideUnicodeRange)]);
+        run_test("&&amp;&a&lt;", "&&&a<", &[]);
+        // Unterminated references are left alone, unless too many digits were read to keep them.
+        run_test("&#00000000000065 x", "&#00000000000065 x", &[(0, MissingSemicolon)]);
+        run_test("&#00000000000000000065 x", "A x", &[(0, MissingSemicolon)]);
+        run_test("&#00000000000000000065;", "A", &[]);
+        run_test("&#x99999999999999999999 x", "\u{FFFD} x",
+                 &[(0, MissingSemicolon), (0, OutsideUnicodeRange)]);
+    }
+
+    #[test]
+    fn entities(){
+        use ::{Entities, UnescapeErrorKind::*};
+        let entities = Entities::new().entity("company", "ACME").entity("notcustom", "!")
+                                      .entity("my-ent", "<>").entity("amp", "and");
+        let run_test = |unescape: Unescape<_>, to: &str, errors: &[(usize, ::UnescapeErrorKind)]| {
+            let mut unescape = unescape.report_errors();
+            let dv = unescape.by_ref().collect::<Vec<_>>();
+            assert_eq!(str::from_utf8(&dv).unwrap(), to);
+            let found = unescape.errors().iter().map(|e| (e.offset(), e.kind()));
+            assert_eq!(found.collect::<Vec<_>>(), errors);
+        };
+        let html = |from: &'static str| Unescape::new(from.bytes()).with_entities(entities.clone());
+        run_test(html("&company;&copy;&my-ent;&amp;"), "ACME\u{a9}<>and", &[]);
+        run_test(html("&notcustom;&notcust;&notin;&not"), "!\u{ac}cust;\u{2209}\u{ac}",
+                 &[(11, MissingSemicolon), (27, MissingSemicolon)]);
+        run_test(html("&company &bogus; &my-ent"), "&company &bogus; &my-ent",
+                 &[(0, MissingSemicolon), (9, UnknownNamedReference), (17, MissingSemicolon)]);
+        run_test(html("&companyandsome;"), "&companyandsome;", &[(0, UnknownNamedReference)]);
+
+        let exclusive = Entities::exclusive().entity("company", "ACME");
+        run_test(Unescape::new("&company;&amp;&#38;".bytes()).with_entities(exclusive.clone()),
+                 "ACME&amp;&", &[(9, UnknownNamedReference)]);
+        run_test(Unescape::xml("&company;&amp;&nbsp;".bytes()).with_entities(exclusive),
+                 "ACME&amp;&nbsp;", &[(9, UnknownNamedReference), (14, UnknownNamedReference)]);
+        run_test(Unescape::xml("&company;&amp;&nbsp;".bytes()).with_entities(entities.clone()),
+                 "ACMEand&nbsp;", &[(14, UnknownNamedReference)]);
+
+        // An empty dictionary changes nothing.
+        for &from in &["&notin; &notit; &amp &ampx &#38;", "&copyright &bogus; &a-b;"] {
+            let expected = Unescape::new(from.bytes()).collect::<Vec<_>>();
+            let found = Unescape::new(from.bytes()).with_entities(Entities::new());
+            assert_eq!(found.collect::<Vec<_>>(), expected);
+        }
+
+        // Names may be any XML names, including the ones with non-ASCII characters.
+        let names = Entities::new().entity("svg:rect", "[]").entity("caf\u{e9}", "coffee")
+                                   .entity("_x.1", "y").entity("\u{3042}", "a");
+        let from = "&svg:rect;&caf\u{e9};&_x.1;&\u{3042};&caf;&caf\u{e9}";
+        let to = "[]coffeeya&caf;&caf\u{e9}";
+        let errors = [(28, UnknownNamedReference), (33, MissingSemicolon)];
+        run_test(Unescape::new(from.bytes()).with_entities(names.clone()), to, &errors);
+        let mut chars = UnescapeChars::new(from.chars()).with_entities(names.clone())
+                                                        .report_errors();
+        assert_eq!(chars.by_ref().collect::<String>(), to);
+        let found = chars.errors().iter().map(|e| (e.offset(), e.kind()));
+        assert_eq!(found.collect::<Vec<_>>(), errors);
+        run_test(Unescape::xml("&svg:rect; &\u{e9};".bytes()).with_entities(names),
+                 "[] &\u{e9};", &[(11, UnknownNamedReference)]);
+        run_test(Unescape::new("&svg:rect; &\u{e9};".bytes()), "&svg:rect; &\u{e9};", &[]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn entities_invalid_name(){
+        ::Entities::new().entity("1st", "first");
+    }
+
+    #[test]
+    fn chars(){
+        let inputs = ["&copy\u{20ac}", "&#169\u{20ac}", "&#x\u{20ac}", "&#\u{e9};",
+                      "&bogus\u{e9};", "\u{e9}&notin\u{e9}&not\u{e9}", "&\u{e9}&amp;\u{1F600}&lt",
+                      "&ab\u{1F600}&#0;", "&amp\u{e9}", "&lt\u{e9};&quot;"];
+        for &from in &inputs {
+            let mut bytes = Unescape::attribute(from.bytes()).report_errors();
+            let expected = String::from_utf8(bytes.by_ref().collect()).unwrap();
+            let mut chars = UnescapeChars::attribute(from.chars()).report_errors();
+            assert_eq!(chars.by_ref().collect::<String>(), expected);
+            assert_eq!(chars.errors(), bytes.errors());
+            let mut bytes = Unescape::xml(from.bytes()).report_errors();
+            let expected = String::from_utf8(bytes.by_ref().collect()).unwrap();
+            let mut chars = UnescapeChars::xml(from.chars()).report_errors();
+            assert_eq!(chars.by_ref().collect::<String>(), expected);
+            assert_eq!(chars.errors(), bytes.errors());
+        }
+    }
+
+    #[test]
+    fn size_hint(){
+        use ::{Entities, unescape_bytes, unescaped_len_upper_bound};
+        let entities = Entities::new().entity("a", "a long replacement");
+        let inputs = ["", "abc", "&nGt;&nLt;&nGt;", "&lt;&#0;&#x1F600;&#000000000000000000000065;",
+                      "&a;&a;&b;&a", "&#x1F600", "&#x&#;&bogus;&amp"];
+        for &from in &inputs {
+            let expected = unescape_bytes(from.as_bytes());
+            assert!(unescaped_len_upper_bound(from.as_bytes()) >= expected.len());
+            let expected = Unescape::new(from.bytes()).with_entities(entities.clone()).count();
+            check_size_hint(Unescape::new(from.bytes()).with_entities(entities.clone()), expected);
+            let expected = UnescapeChars::new(from.chars()).with_entities(entities.clone()).count();
+            let unescape = UnescapeChars::new(from.chars()).with_entities(entities.clone());
+            check_size_hint(unescape, expected);
+        }
+    }
+
+    #[test]
+    fn borrowed(){
+        assert!(matches!(unescape_str("Hello world!"), Cow::Borrowed("Hello world!")));
+        assert!(matches!(unescape_str("Hello &amp; world!"), Cow::Owned(_)));
+    }
+
+    #[test]
+    fn streaming(){
+        use ::{Entities, StreamingUnescaper};
+        let inputs = ["&lt;a&gt; &amp &ampx &notin;&notit; &#x1F600;&#65 &#0; &bogus; &",
+                      "&#0000000000000000000000000065;&#xFFFFFFFFFF &nbsp;&nbs &a;&a &amp;&quot;"];
+        let entities = Entities::new().entity("a", "replacement");
+        for &from in &inputs {
+            let from = from.as_bytes();
+            let unescapers = || vec![
+                (Unescape::new(from.iter().cloned()), StreamingUnescaper::new()),
+                (Unescape::attribute(from.iter().cloned()), StreamingUnescaper::attribute()),
+                (Unescape::xml(from.iter().cloned()), StreamingUnescaper::xml()),
+                (Unescape::new(from.iter().cloned()).with_entities(entities.clone()),
+                 StreamingUnescaper::new().with_entities(entities.clone())),
+            ];
+            // Try every way of splitting the input into three chunks.
+            for i in 0..from.len() + 1 {
+                for j in i..from.len() + 1 {
+                    for (unescape, streaming) in unescapers() {
+                        let mut unescape = unescape.report_errors();
+                        let mut streaming = streaming.report_errors();
+                        let expected = unescape.by_ref().collect::<Vec<_>>();
+                        let mut unescaped = Vec::new();
+                        streaming.feed(&from[..i], &mut unescaped);
+                        streaming.feed(&from[i..j], &mut unescaped);
+                        assert!(streaming.errors().len() <= unescape.errors().len());
+                        streaming.feed(&from[j..], &mut unescaped);
+                        assert_eq!(streaming.finish(&mut unescaped), unescape.errors());
+                        assert_eq!(unescaped, expected);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn slices_match_iterator(){
+        use ::{unescape_bytes, unescaped_len_upper_bound};
+        use ::UnescapeErrorKind::ControlCharacter;
+        let pieces = ["Hello world, this run has no references at all. ", "&amp;", "&lt", "&nGt;",
+                      "&#x1F600;", "&#65", "&", "&&", "&bogus;", "\u{e9}", "&notin;", "&notit;",
+                      "&not", "&amp=", "&lt;&gt", "&x"];
+        // Mostly plain text with references here and there, in runs of every length.
+        let string = random().take(500).map(|r| pieces[r % pieces.len()]).collect::<String>();
+        for start in 0..70 {
+            let bytes = &string.as_bytes()[start..];
+            let mut unescape = Unescape::new(bytes.iter().cloned()).report_errors();
+            let dv = unescape.by_ref().collect::<Vec<_>>();
+            let unescaped = unescape_bytes(bytes);
+            assert_eq!(&*unescaped, &*dv);
+            let mut streaming = StreamingUnescaper::new().report_errors();
+            let mut out = Vec::new();
+            streaming.feed(bytes, &mut out);
+            assert_eq!(&*streaming.finish(&mut out), unescape.errors());
+            let attribute = Unescape::attribute(bytes.iter().cloned()).collect::<Vec<_>>();
+            let mut streaming = StreamingUnescaper::attribute();
+            let mut out = Vec::new();
+            streaming.feed(bytes, &mut out);
+            streaming.finish(&mut out);
+            assert_eq!(attribute, out);
+            assert!(unescaped_len_upper_bound(bytes) >= dv.len());
+            // Strict unescaping reports more unknown names, but fewer numeric references.
+            match strict_unescape(bytes) {
+                Ok(strict) => {
+                    assert_eq!(strict, dv);
+                    assert!(unescape.errors().iter().all(|e| e.kind() == ControlCharacter));
+                },
+                Err(error) => assert!(error.offset() < bytes.len())
+            }
+        }
+    }
+
     #[test]
     fn dec_escape(){
         run_test("&#38;", "&");
@@ -270,6 +1639,14 @@ mod test {
         run_test("&#11822;&#33;", "⸮!");
         run_test("&#65533;", "�"); // REPLACEMENT CHARACTER intended here 😉
         run_test("&#1234567890;", "�"); // REPLACEMENT CHARACTER intended here 😉
+        run_test("&#1234567890$", "&#1234567890$");
+        run_test("&#00000000000000000033$", "!$");
+        run_test("&#0000000000000065$", "A$");
+        run_test("&#00000000000065$", "&#00000000000065$");
+        run_test("&#0000000000033$", "&#0000000000033$");
+        run_test("&#00000000000000000033;", "!");
+        run_test("&#1234567890", "&#1234567890");
+        run_test("&#12345678901234567890$", "�$"); // REPLACEMENT CHARACTER intended here 😉
     }
 
     #[test]
@@ -288,6 +1665,38 @@ mod test {
         run_test("&#x2E2e;&#x21;", "⸮!");
         run_test("&#xfffd;", "�"); // REPLACEMENT CHARACTER intended here 😉
         run_test("&#x1234567890ABCDEF;", "�"); // REPLACEMENT CHARACTER intended here 😉
+        run_test("&#x1234567890ABCDEF&#x21;", "�!"); // REPLACEMENT CHARACTER intended here 😉
+        run_test("&#x00000000000000000021&", "!&");
+        run_test("&#x0000000000021&", "&#x0000000000021&");
+        run_test("&#X00000000000000000021;", "!");
+        run_test("&#x110000$", "&#x110000$");
+        run_test("&#x10FFFF0$", "&#x10FFFF0$");
+    }
+
+    #[test]
+    fn long_numeric_escape(){
+        for &(prefix, digit) in &[("&#", b'9'), ("&#x", b'F'), ("&#", b'0'), ("&#x", b'0')] {
+            let digits = iter::repeat_n(digit, 4 << 20);
+            let input = prefix.bytes().chain(digits).chain(Some(b'$')).collect::<Vec<_>>();
+            let mut unescape = Unescape::new(input.iter().cloned());
+            let mut output = Vec::new();
+            while let Some(b) = unescape.next() {
+                assert!(unescape.buffer.capacity() <= 2 * LONGEST_BUFFERED_NUMERIC_REFERENCE);
+                let capacity = unescape.decoder.pending.capacity();
+                assert!(capacity <= 2 * LONGEST_BUFFERED_NUMERIC_REFERENCE);
+                output.push(b);
+            }
+            // The reference is too long to be emitted unchanged, so it is decoded regardless.
+            assert_eq!(output, "\u{FFFD}$".as_bytes());
+            let mut streaming = StreamingUnescaper::new();
+            let mut output = Vec::new();
+            for chunk in input.chunks(4096) {
+                streaming.feed(chunk, &mut output);
+                assert!(output.capacity() <= 2 * LONGEST_BUFFERED_NUMERIC_REFERENCE);
+            }
+            streaming.finish(&mut output);
+            assert_eq!(output, "\u{FFFD}$".as_bytes());
+        }
     }
 
     #[test]
@@ -307,5 +1716,9 @@ mod test {
         run_test("&UnderParenthesis;", "⏝");
         run_test("&underParenthesis;", "&underParenthesis;");
         run_test("&Underparenthesis;", "&Underparenthesis;");
+        run_test("&notin;", "∉");
+        run_test("&notit;", "¬it;");
+        run_test("&copyright", "©right");
+        run_test("&ampamp;", "&amp;");
     }
 }
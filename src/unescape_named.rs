@@ -7,6 +7,7 @@ pub enum RefMatch {
 pub struct Trie(&'static [Option<&'static Trie>], u16, u8);
 static NULL_TRIE: Trie = Trie(&[], !0, !0);
 
+#[derive(Clone, Copy)]
 pub struct Matcher(&'static Trie);
 
 impl Matcher {
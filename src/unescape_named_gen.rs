@@ -1,9 +1,6 @@
 // This file is autogenerated by generate.py, do not modify
 
-/// Length of the longest supported/known character reference name.
-pub const LONGEST_NAMED_REFERENCE : usize = 32;
-
-pub static DECODED: [&'static [u8]; 2231] = [
+pub static DECODED: [&[u8]; 2231] = [
 b"\xC3\x86", b"\xC3\x86", b"\x26", b"\x26", b"\xC3\x81", b"\xC3\x81",
 b"\xC4\x82", b"\xC3\x82", b"\xC3\x82", b"\xD0\x90", b"\xF0\x9D\x94\x84", b"\xC3\x80",
 b"\xC3\x80", b"\xCE\x91", b"\xC4\x80", b"\xE2\xA9\x93", b"\xC4\x84", b"\xF0\x9D\x94\xB8",
@@ -0,0 +1,82 @@
+use core::str;
+
+/// Length of the longest hexadecimal reference, `&#x10FFFF;`.
+pub const LONGEST_HEX_REFERENCE : usize = 10;
+
+/// Read the rest of the UTF-8 sequence started by `first` from the iterator.
+///
+/// Returns the decoded character, or the bytes of the invalid sequence and their count. As in
+/// `String::from_utf8_lossy`, every maximal prefix of a valid sequence is one invalid sequence.
+/// The byte which cut an incomplete sequence short is stored in `peeked`, to be read next.
+pub fn read_char<I: Iterator<Item=u8>>(first: u8, inner: &mut I, peeked: &mut Option<u8>)
+                                      -> Result<char, ([u8; 4], usize)> {
+    let (width, second) = match first {
+        0x00..=0x7F => return Ok(first as char),
+        0xC2..=0xDF => (2, 0x80..=0xBF),
+        0xE0 => (3, 0xA0..=0xBF),
+        0xED => (3, 0x80..=0x9F),
+        0xE1..=0xEF => (3, 0x80..=0xBF),
+        0xF0 => (4, 0x90..=0xBF),
+        0xF4 => (4, 0x80..=0x8F),
+        0xF1..=0xF3 => (4, 0x80..=0xBF),
+        _ => return Err(([first, 0, 0, 0], 1))
+    };
+    let mut sequence = [first, 0, 0, 0];
+    let mut len = 1;
+    while len < width {
+        let valid = if len == 1 { second.clone() } else { 0x80..=0xBF };
+        match inner.next() {
+            Some(b) if valid.contains(&b) => {
+                sequence[len] = b;
+                len += 1;
+            },
+            next => {
+                *peeked = next;
+                return Err((sequence, len));
+            }
+        }
+    }
+    match str::from_utf8(&sequence[..len]).ok().and_then(|s| s.chars().next()) {
+        Some(ch) => Ok(ch),
+        None => Err((sequence, len))
+    }
+}
+
+/// Whether a numeric reference to the character decodes back to it.
+///
+/// HTML5 decodes the references to the C1 controls as windows-1252 and the ones to the
+/// noncharacters to U+FFFD, so these characters cannot be escaped.
+pub fn survives_reference(ch: char) -> bool {
+    match ch as u32 {
+        0x80..=0x9F | 0xFDD0..=0xFDEF => false,
+        codepoint => codepoint & 0xFFFE != 0xFFFE
+    }
+}
+
+/// Length of the hexadecimal reference to the character.
+#[cfg(feature = "alloc")]
+pub fn hex_reference_len(ch: char) -> usize {
+    let digits = (32 - (ch as u32).leading_zeros() as usize).div_ceil(4).max(1);
+    "&#x;".len() + digits
+}
+
+/// Write the hexadecimal reference to the character, e.g. `&#x1F600;`, to the start of `out`,
+/// which must be at least `LONGEST_HEX_REFERENCE` bytes long.
+///
+/// Returns the length of the reference.
+pub fn write_hex_reference(ch: char, out: &mut [u8]) -> usize {
+    let mut digits = [0; 6];
+    let mut value = ch as u32;
+    let mut len = 0;
+    while value != 0 || len == 0 {
+        digits[len] = b"0123456789ABCDEF"[(value & 0xF) as usize];
+        value >>= 4;
+        len += 1;
+    }
+    out[..3].copy_from_slice(b"&#x");
+    for (i, &digit) in digits[..len].iter().rev().enumerate() {
+        out[3 + i] = digit;
+    }
+    out[3 + len] = b';';
+    4 + len
+}
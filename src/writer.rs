@@ -0,0 +1,308 @@
+use alloc::vec::Vec;
+use core::cmp;
+use std::io::{self, Write};
+
+use escape::{EscapeContext, EscapeTable};
+use unescape::Decoder;
+
+
+/// Amount of input processed by the I/O adaptors at once.
+pub const CHUNK_SIZE : usize = 8 * 1024;
+
+/// A writer which escapes all the data written to it before passing it on to the inner writer.
+///
+/// The same tables as in `Escape` are used, so `EscapeWriter::new(w)` produces the same output as
+/// `Escape::new` would.
+///
+/// Output is forwarded to the inner writer in chunks of up to several kilobytes. Escaped output
+/// which could not be written out because of an error is kept and written out by the next call to
+/// `write` or `flush`.
+///
+/// # Usage
+///
+/// ```
+/// use std::io::Write;
+/// use marksman_escape::EscapeWriter;
+/// let mut writer = EscapeWriter::new(Vec::new());
+/// write!(writer, "<p>{}</p>", "Hello & goodbye").unwrap();
+/// let escaped = writer.into_inner().unwrap();
+/// assert_eq!(&escaped[..], &b"&lt;p&gt;Hello &amp; goodbye&lt;/p&gt;"[..]);
+/// ```
+pub struct EscapeWriter<'t, W: Write> {
+    inner: Option<W>,
+    table: &'t EscapeTable<'t>,
+    buffer: Vec<u8>,
+}
+
+impl<'t, W: Write> EscapeWriter<'t, W> {
+    /// Create a writer which escapes the same characters as `Escape::new` does.
+    pub fn new(inner: W) -> EscapeWriter<'t, W> {
+        EscapeWriter::with_table(inner, EscapeTable::conservative())
+    }
+
+    /// Create a writer which escapes only the characters significant in the given context.
+    pub fn with_context(inner: W, context: EscapeContext) -> EscapeWriter<'t, W> {
+        EscapeWriter::with_table(inner, context.table())
+    }
+
+    /// Create a writer which escapes characters according to the provided table.
+    pub fn with_table(inner: W, table: &'t EscapeTable<'t>) -> EscapeWriter<'t, W> {
+        EscapeWriter {
+            inner: Some(inner),
+            table,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Get a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.as_ref().unwrap()
+    }
+
+    /// Get a mutable reference to the inner writer.
+    ///
+    /// Writing directly to the inner writer might interleave with the escaped output which is yet
+    /// to be written out.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.as_mut().unwrap()
+    }
+
+    /// Write out the pending output and return the inner writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        write_pending(self.inner.as_mut().unwrap(), &mut self.buffer)?;
+        Ok(self.inner.take().unwrap())
+    }
+}
+
+impl<'t, W: Write> Write for EscapeWriter<'t, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let inner = self.inner.as_mut().unwrap();
+        write_pending(inner, &mut self.buffer)?;
+        let consumed = cmp::min(buf.len(), CHUNK_SIZE);
+        self.table.escape_to(&buf[..consumed], &mut self.buffer);
+        // The input is consumed already. If writing it out fails, the rest is retried and the error
+        // reported by the next call.
+        let _ = write_pending(inner, &mut self.buffer);
+        Ok(consumed)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let inner = self.inner.as_mut().unwrap();
+        write_pending(inner, &mut self.buffer)?;
+        inner.flush()
+    }
+}
+
+impl<'t, W: Write> Drop for EscapeWriter<'t, W> {
+    fn drop(&mut self) {
+        if let Some(ref mut inner) = self.inner {
+            // Errors cannot be reported from here. Use `flush` or `into_inner` to observe them.
+            let _ = write_pending(inner, &mut self.buffer);
+        }
+    }
+}
+
+/// A writer which unescapes all the data written to it before passing it on to the inner writer.
+///
+/// Character references may be split across `write` calls. A partially written reference is
+/// kept until the rest of it is written and resolved once the writer is finished with
+/// `into_inner` or dropped, so the output is the same as `Unescape` would produce for all of the
+/// written data concatenated. `flush` cannot resolve such a reference, because more of it might
+/// still be written, so it only writes out the data decoded so far.
+///
+/// # Usage
+///
+/// ```
+/// use std::io::Write;
+/// use marksman_escape::UnescapeWriter;
+/// let mut writer = UnescapeWriter::new(Vec::new());
+/// writer.write_all(b"&lt;hello&g").unwrap();
+/// writer.write_all(b"t;&amp").unwrap();
+/// let unescaped = writer.into_inner().unwrap();
+/// assert_eq!(&unescaped[..], &b"<hello>&"[..]);
+/// ```
+pub struct UnescapeWriter<W: Write> {
+    inner: Option<W>,
+    decoder: Decoder,
+    buffer: Vec<u8>,
+}
+
+impl<W: Write> UnescapeWriter<W> {
+    /// Create a writer which unescapes the same character references as `Unescape` does.
+    pub fn new(inner: W) -> UnescapeWriter<W> {
+        UnescapeWriter {
+            inner: Some(inner),
+            decoder: Decoder::new(),
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Get a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.as_ref().unwrap()
+    }
+
+    /// Get a mutable reference to the inner writer.
+    ///
+    /// Writing directly to the inner writer might interleave with the unescaped output which is
+    /// yet to be written out.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.as_mut().unwrap()
+    }
+
+    /// Resolve the partially written character reference, write out the pending output and return
+    /// the inner writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.decoder.finish(&mut self.buffer);
+        write_pending(self.inner.as_mut().unwrap(), &mut self.buffer)?;
+        Ok(self.inner.take().unwrap())
+    }
+}
+
+impl<W: Write> Write for UnescapeWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let inner = self.inner.as_mut().unwrap();
+        write_pending(inner, &mut self.buffer)?;
+        let consumed = cmp::min(buf.len(), CHUNK_SIZE);
+        self.decoder.feed(&buf[..consumed], &mut self.buffer);
+        // The input is consumed already. If writing it out fails, the rest is retried and the error
+        // reported by the next call.
+        let _ = write_pending(inner, &mut self.buffer);
+        Ok(consumed)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let inner = self.inner.as_mut().unwrap();
+        write_pending(inner, &mut self.buffer)?;
+        inner.flush()
+    }
+}
+
+impl<W: Write> Drop for UnescapeWriter<W> {
+    fn drop(&mut self) {
+        if let Some(ref mut inner) = self.inner {
+            self.decoder.finish(&mut self.buffer);
+            // Errors cannot be reported from here. Use `flush` or `into_inner` to observe them.
+            let _ = write_pending(inner, &mut self.buffer);
+        }
+    }
+}
+
+/// Write the buffer to the writer, removing everything that was written out from the buffer even
+/// if an error occurs.
+fn write_pending<W: Write>(inner: &mut W, buffer: &mut Vec<u8>) -> io::Result<()> {
+    let mut written = 0;
+    let mut ret = Ok(());
+    while written < buffer.len() {
+        match inner.write(&buffer[written..]) {
+            Ok(0) => {
+                ret = Err(io::Error::new(io::ErrorKind::WriteZero,
+                                         "failed to write the buffered data"));
+                break;
+            },
+            Ok(n) => written += n,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {},
+            Err(e) => {
+                ret = Err(e);
+                break;
+            }
+        }
+    }
+    buffer.drain(..written);
+    ret
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::io::{self, Write};
+    use std::prelude::v1::*;
+    use ::{Escape, EscapeContext, EscapeWriter, Unescape, UnescapeWriter};
+    use unescape::LONGEST_BUFFERED_NUMERIC_REFERENCE;
+
+    /// A writer accepting at most 3 bytes at a time and failing every other call.
+    struct Flaky(Vec<u8>, bool);
+
+    impl Write for Flaky {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.1 = !self.1;
+            if self.1 {
+                return Err(io::Error::other("flaky"));
+            }
+            let len = ::std::cmp::min(buf.len(), 3);
+            self.0.extend_from_slice(&buf[..len]);
+            Ok(len)
+        }
+        fn flush(&mut self) -> io::Result<()> { Ok(()) }
+    }
+
+    #[test]
+    fn escape_writer(){
+        let input = "<hello>&{world}</hello>".repeat(1000);
+        let expected = Escape::new(input.bytes()).collect::<Vec<_>>();
+        let mut writer = EscapeWriter::new(Vec::new());
+        for chunk in input.as_bytes().chunks(7) {
+            writer.write_all(chunk).unwrap();
+        }
+        assert_eq!(writer.into_inner().unwrap(), expected);
+
+        let mut writer = EscapeWriter::with_context(Vec::new(), EscapeContext::DoubleQuotedAttr);
+        writer.write_all(b"<\"a\">").unwrap();
+        assert_eq!(writer.into_inner().unwrap(), b"<&#34;a&#34;>");
+    }
+
+    #[test]
+    fn escape_writer_errors(){
+        let mut writer = EscapeWriter::new(Flaky(Vec::new(), false));
+        let mut input = &b"<hello>&world</hello>"[..];
+        while !input.is_empty() {
+            if let Ok(n) = writer.write(input) {
+                input = &input[n..];
+            }
+        }
+        while writer.flush().is_err() {}
+        assert_eq!(writer.get_ref().0, b"&lt;hello&gt;&amp;world&lt;/hello&gt;");
+    }
+
+    #[test]
+    fn unescape_writer_split(){
+        let inputs = ["&lt;hello&gt;&amp;world&#60;/hello&#x3e;", "&AMP&notin;&&amp",
+                      "&#x1234567890ABCDEF;", "&#12345$&#;&#x;",
+                      "&UnderParenthesis;&underParenthesis;", "&amp"];
+        for input in &inputs {
+            let expected = Unescape::new(input.bytes()).collect::<Vec<_>>();
+            for i in 0..input.len() + 1 {
+                for j in i..input.len() + 1 {
+                    let mut writer = UnescapeWriter::new(Vec::new());
+                    writer.write_all(&input.as_bytes()[..i]).unwrap();
+                    writer.flush().unwrap();
+                    writer.write_all(&input.as_bytes()[i..j]).unwrap();
+                    writer.write_all(&input.as_bytes()[j..]).unwrap();
+                    assert_eq!(writer.into_inner().unwrap(), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unescape_writer_drop(){
+        let mut output = Vec::new();
+        {
+            let mut writer = UnescapeWriter::new(&mut output);
+            writer.write_all(b"&amp;&#x2E2E").unwrap();
+        }
+        assert_eq!(output, b"&&#x2E2E");
+    }
+
+    #[test]
+    fn unescape_writer_long_reference(){
+        let mut writer = UnescapeWriter::new(Vec::new());
+        writer.write_all(b"&#x").unwrap();
+        for _ in 0..1024 {
+            writer.write_all(&[b'0'; 4096]).unwrap();
+        }
+        writer.write_all(b"$").unwrap();
+        assert!(writer.buffer.capacity() <= 2 * LONGEST_BUFFERED_NUMERIC_REFERENCE);
+        assert_eq!(writer.into_inner().unwrap(), "\u{FFFD}$".as_bytes());
+    }
+}